@@ -0,0 +1,262 @@
+//! Control groups resolution for the current process.
+//!
+//! See `cgroups(7)` for details on both hierarchy versions.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::unescape_octal;
+
+/// Control group hierarchy version.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Version {
+    /// cgroup v1, each controller is mounted separately.
+    V1,
+    /// cgroup v2, unified hierarchy.
+    V2,
+}
+
+/// Describes where the container-aware value came from.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum LimitSource {
+    /// Value is taken from the host system, because process
+    /// is not constrained by any control group.
+    Host,
+    /// Value is taken from the cgroup v1 file at the given path.
+    CgroupV1(PathBuf),
+    /// Value is taken from the cgroup v2 file at the given path.
+    CgroupV2(PathBuf),
+}
+
+impl LimitSource {
+    /// Returns path to the control group file the value was read from.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            LimitSource::Host => None,
+            LimitSource::CgroupV1(path) | LimitSource::CgroupV2(path) => Some(path),
+        }
+    }
+}
+
+/// Returns limit source for the control group file `path` of the hierarchy `version`.
+pub fn source(version: Version, path: PathBuf) -> LimitSource {
+    match version {
+        Version::V1 => LimitSource::CgroupV1(path),
+        Version::V2 => LimitSource::CgroupV2(path),
+    }
+}
+
+/// Control group of the current process for some particular controller.
+#[derive(Debug, Clone)]
+pub struct Cgroup {
+    version: Version,
+    mount_point: PathBuf,
+    path: PathBuf,
+}
+
+impl Cgroup {
+    /// Returns hierarchy version.
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// Returns absolute path to the control group directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns control group directory and all its parent directories
+    /// up to the hierarchy mount point, starting from the control group itself.
+    pub fn ancestors(&self) -> impl Iterator<Item = &Path> {
+        let mount_point = self.mount_point.as_path();
+
+        self.path
+            .ancestors()
+            .take_while(move |path| path.starts_with(mount_point))
+    }
+}
+
+#[derive(Debug)]
+struct Mount {
+    root: String,
+    mount_point: PathBuf,
+    fs_type: String,
+    super_options: String,
+}
+
+// Example:
+// 36 35 98:0 /mnt1 /mnt2 rw,noatime master:1 - ext3 /dev/root rw,errors=continue
+fn parse_mount(line: &str) -> Option<Mount> {
+    let mut halves = line.splitn(2, " - ");
+    let mut head = halves.next()?.split(' ').skip(3);
+    let mut tail = halves.next()?.split(' ');
+
+    let root = unescape_octal(head.next()?).into_owned();
+    let mount_point = PathBuf::from(unescape_octal(head.next()?).as_ref());
+    let fs_type = tail.next()?.to_string();
+    let _source = tail.next()?;
+    let super_options = tail.next()?.to_string();
+
+    Some(Mount {
+        root,
+        mount_point,
+        fs_type,
+        super_options,
+    })
+}
+
+fn resolve(version: Version, mount: Mount, cgroup_path: &str) -> Cgroup {
+    // Mount root is not `/` if the hierarchy was bind-mounted into the container
+    // without cgroup namespaces; process cgroup path is still relative to the hierarchy root.
+    let relative = if mount.root == "/" {
+        Some(cgroup_path)
+    } else {
+        cgroup_path
+            .strip_prefix(mount.root.as_str())
+            .filter(|rest| rest.is_empty() || rest.starts_with('/'))
+    };
+
+    let path = match relative {
+        Some(relative) => mount.mount_point.join(relative.trim_start_matches('/')),
+        // Process cgroup is outside of the visible hierarchy part,
+        // the best we can do is to use the mount point itself.
+        None => mount.mount_point.clone(),
+    };
+
+    Cgroup {
+        version,
+        mount_point: mount.mount_point,
+        path,
+    }
+}
+
+// Controllers are enabled for the cgroup v2 group via its parent `cgroup.subtree_control` file,
+// while the hierarchy root group has all of the `cgroup.controllers` available.
+fn controllers_file(cgroup: &Cgroup) -> PathBuf {
+    match cgroup
+        .path
+        .parent()
+        .filter(|parent| parent.starts_with(&cgroup.mount_point))
+    {
+        Some(parent) => parent.join("cgroup.subtree_control"),
+        None => cgroup.mount_point.join("cgroup.controllers"),
+    }
+}
+
+/// Resolves the current process control group for the `controller` (ex. `memory` or `cpu`).
+///
+/// cgroup v1 hierarchy is preferred if controller is attached to it,
+/// otherwise cgroup v2 hierarchy is used.
+///
+/// Returns `Ok(None)` if process is not a member of any control group
+/// with this controller, if hierarchy is not mounted
+/// or if kernel does not support control groups at all.
+pub fn cgroup(procfs_root: &Path, controller: &str) -> io::Result<Option<Cgroup>> {
+    let memberships = match fs::read_to_string(procfs_root.join("self/cgroup")) {
+        Ok(contents) => contents,
+        // Kernel was built without `CONFIG_CGROUPS`
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let mountinfo = fs::read_to_string(procfs_root.join("self/mountinfo"))?;
+
+    let mut unified = None;
+    // Example:
+    // 4:memory:/docker/4d2f
+    // 0::/user.slice/user-1000.slice
+    for line in memberships.lines() {
+        let mut parts = line.splitn(3, ':');
+        let (controllers, path) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(_), Some(controllers), Some(path)) => (controllers, path),
+            _ => continue,
+        };
+
+        if controllers.is_empty() {
+            unified = Some(path);
+        } else if controllers.split(',').any(|name| name == controller) {
+            let mount = mountinfo.lines().filter_map(parse_mount).find(|mount| {
+                mount.fs_type == "cgroup"
+                    && mount
+                        .super_options
+                        .split(',')
+                        .any(|name| name == controller)
+            });
+            if let Some(mount) = mount {
+                return Ok(Some(resolve(Version::V1, mount, path)));
+            }
+        }
+    }
+
+    if let Some(path) = unified {
+        let mount = mountinfo
+            .lines()
+            .filter_map(parse_mount)
+            .find(|mount| mount.fs_type == "cgroup2");
+        if let Some(mount) = mount {
+            let cgroup = resolve(Version::V2, mount, path);
+            // Controller might be not enabled for the process group
+            let controllers = fs::read_to_string(controllers_file(&cgroup)).unwrap_or_default();
+            if controllers
+                .split_whitespace()
+                .any(|name| name == controller)
+            {
+                return Ok(Some(cgroup));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{controllers_file, parse_mount, resolve, Version};
+
+    #[test]
+    fn test_resolve_bind_mounted() {
+        let line =
+            "1234 1200 0:32 /docker/4d2f /sys/fs/cgroup/memory ro,nosuid - cgroup cgroup rw,memory";
+        let mount = parse_mount(line).unwrap();
+        let cgroup = resolve(Version::V1, mount, "/docker/4d2f");
+
+        assert_eq!(cgroup.path(), Path::new("/sys/fs/cgroup/memory"));
+        assert_eq!(cgroup.ancestors().count(), 1);
+    }
+
+    #[test]
+    fn test_resolve_nested() {
+        let line = "42 32 0:38 / /sys/fs/cgroup rw,relatime - cgroup2 cgroup2 rw";
+        let mount = parse_mount(line).unwrap();
+        let cgroup = resolve(Version::V2, mount, "/kubepods/pod1/abc");
+
+        assert_eq!(cgroup.path(), Path::new("/sys/fs/cgroup/kubepods/pod1/abc"));
+        assert_eq!(
+            cgroup.ancestors().collect::<Vec<_>>(),
+            vec![
+                Path::new("/sys/fs/cgroup/kubepods/pod1/abc"),
+                Path::new("/sys/fs/cgroup/kubepods/pod1"),
+                Path::new("/sys/fs/cgroup/kubepods"),
+                Path::new("/sys/fs/cgroup"),
+            ]
+        );
+        assert_eq!(
+            controllers_file(&cgroup),
+            Path::new("/sys/fs/cgroup/kubepods/pod1/cgroup.subtree_control")
+        );
+    }
+
+    #[test]
+    fn test_controllers_file_root() {
+        let line = "42 32 0:38 / /sys/fs/cgroup rw,relatime - cgroup2 cgroup2 rw";
+        let mount = parse_mount(line).unwrap();
+        let cgroup = resolve(Version::V2, mount, "/");
+
+        assert_eq!(
+            controllers_file(&cgroup),
+            Path::new("/sys/fs/cgroup/cgroup.controllers")
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! Linux-specific routines used across `heim` crates.
+
+use std::borrow::Cow;
+
+use crate::Result;
+
+pub mod cgroup;
+
+/// Parses CPU list format used by the kernel, ex. `0-7,16-23`.
+///
+/// See `cpulist_parse` in the `lib/bitmap.c` kernel source.
+pub fn parse_cpu_list(list: &str) -> Result<Vec<u32>> {
+    let mut cpus = Vec::new();
+
+    for range in list.trim().split(',').filter(|range| !range.is_empty()) {
+        let mut bounds = range.splitn(2, '-');
+        match (bounds.next(), bounds.next()) {
+            (Some(start), Some(end)) => {
+                let start = start.parse::<u32>()?;
+                let end = end.parse::<u32>()?;
+                cpus.extend(start..=end);
+            }
+            (Some(cpu), None) => cpus.push(cpu.parse::<u32>()?),
+            _ => unreachable!(),
+        }
+    }
+
+    Ok(cpus)
+}
+
+/// Returns option selected with brackets in the sysfs multiple choice files,
+/// ex. `madvise` for the `always [madvise] never`.
+pub fn parse_selected(value: &str) -> Option<&str> {
+    value
+        .split_whitespace()
+        .find(|option| option.starts_with('['))
+        .and_then(|option| option.strip_prefix('['))
+        .and_then(|option| option.strip_suffix(']'))
+}
+
+/// Decodes octal escape sequences (ex. `\040` for space) used by the kernel
+/// in the `/proc/mounts`, `/proc/self/mountinfo` and `/proc/swaps` files.
+///
+/// See `mangle` function in the `fs/seq_file.c` kernel source.
+pub fn unescape_octal(value: &str) -> Cow<'_, str> {
+    if !value.contains('\\') {
+        return Cow::Borrowed(value);
+    }
+
+    let bytes = value.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let escaped = bytes.get(idx + 1..idx + 4).and_then(|digits| {
+            if bytes[idx] == b'\\' && digits.iter().all(|digit| (b'0'..=b'7').contains(digit)) {
+                let digits = std::str::from_utf8(digits).ok()?;
+                u8::from_str_radix(digits, 8).ok()
+            } else {
+                None
+            }
+        });
+
+        match escaped {
+            Some(byte) => {
+                result.push(byte);
+                idx += 4;
+            }
+            None => {
+                result.push(bytes[idx]);
+                idx += 1;
+            }
+        }
+    }
+
+    Cow::Owned(String::from_utf8_lossy(&result).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_cpu_list, parse_selected, unescape_octal};
+
+    #[test]
+    fn test_parse_cpu_list() {
+        assert_eq!(parse_cpu_list("0\n").unwrap(), vec![0]);
+        assert_eq!(
+            parse_cpu_list("0-2,8,10-11\n").unwrap(),
+            vec![0, 1, 2, 8, 10, 11]
+        );
+        assert!(parse_cpu_list("\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_selected() {
+        assert_eq!(parse_selected("[lzo-rle] lzo lz4 \n"), Some("lzo-rle"));
+        assert_eq!(parse_selected("none\n"), None);
+    }
+
+    #[test]
+    fn test_unescape_octal() {
+        assert_eq!(unescape_octal("/mnt/data"), "/mnt/data");
+        assert_eq!(unescape_octal(r"/mnt/my\040disk"), "/mnt/my disk");
+        assert_eq!(unescape_octal(r"/a\011b\134c\012"), "/a\tb\\c\n");
+        assert_eq!(unescape_octal(r"/not\08escape\"), r"/not\08escape\");
+    }
+}
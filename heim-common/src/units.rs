@@ -3,7 +3,7 @@
 //! Check out the [`uom`](https://docs.rs/uom/) crate docs of how to use them.
 
 pub use uom::si::f32::{Ratio, ThermodynamicTemperature};
-pub use uom::si::f64::Time;
+pub use uom::si::f64::{InformationRate, Time};
 pub use uom::si::u64::{Frequency, Information};
 pub use uom::si::{
     frequency, information, information_rate, ratio, thermodynamic_temperature, time,
@@ -0,0 +1,83 @@
+use heim_common::prelude::*;
+
+#[cfg(target_os = "linux")]
+use crate::sys;
+
+pub use heim_common::sys::linux::cgroup::LimitSource;
+
+/// CPU limits as seen from inside of the container.
+///
+/// Effective CPU count is the lowest value of the host logical CPUs count,
+/// CPU bandwidth quota (rounded up) and the number of CPUs in the allowed cpuset.
+#[derive(Debug, Clone)]
+pub struct ContainerCpu {
+    pub(crate) count: u64,
+    pub(crate) count_source: LimitSource,
+    pub(crate) quota: Option<f64>,
+    pub(crate) quota_source: LimitSource,
+    pub(crate) cpus: Option<Vec<u32>>,
+    pub(crate) cpus_source: LimitSource,
+}
+
+impl ContainerCpu {
+    /// Returns effective amount of logical CPUs available for the current process.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns where the [count](#method.count) value came from.
+    pub fn count_source(&self) -> &LimitSource {
+        &self.count_source
+    }
+
+    /// Returns CPU bandwidth quota as a fraction of CPUs, ex. `1.5` for one and a half CPU.
+    ///
+    /// Control group quotas are checked for the current process group
+    /// and all its parent groups, the lowest one wins.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if quota is not set.
+    pub fn quota(&self) -> Option<f64> {
+        self.quota
+    }
+
+    /// Returns where the [quota](#method.quota) value came from.
+    pub fn quota_source(&self) -> &LimitSource {
+        &self.quota_source
+    }
+
+    /// Returns logical CPU numbers the current process is allowed to run on.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if process is not a member of the cpuset control group.
+    pub fn cpus(&self) -> Option<&[u32]> {
+        self.cpus.as_deref()
+    }
+
+    /// Returns where the [cpus](#method.cpus) value came from.
+    pub fn cpus_source(&self) -> &LimitSource {
+        &self.cpus_source
+    }
+}
+
+/// Returns [CPU limits] for the control group of the current process.
+///
+/// Both cgroup v1 (`cpu.cfs_quota_us`, `cpuset.effective_cpus`)
+/// and cgroup v2 (`cpu.max`, `cpuset.cpus.effective`) hierarchies are supported.
+///
+/// Unlike the [logical_count](../../fn.logical_count.html) function,
+/// which always reports host CPUs, this one should be used
+/// by applications running inside of containers.
+///
+/// [CPU limits]: ./struct.ContainerCpu.html
+pub async fn container_cpu() -> Result<ContainerCpu> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::container_cpu().await
+        } else {
+            unimplemented!("For documentation rendering")
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use std::ops;
+
+use heim_common::prelude::*;
+use heim_common::units::{time, Time};
+
+#[cfg(target_os = "linux")]
+use crate::sys;
+
+/// Scheduler statistics for one logical CPU.
+///
+/// Values are cumulative since boot, subtract two samples
+/// in order to get statistics for the sampling window:
+///
+/// ```rust,no_run
+/// # use heim_cpu::os::linux::SchedStat;
+/// # fn check(before: SchedStat, after: SchedStat) {
+/// let delta = after - before;
+/// if let Some(wait) = delta.average_wait() {
+///     println!("CPU {} average run-queue wait: {:?}", delta.cpu(), wait);
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SchedStat {
+    pub(crate) cpu: u32,
+    pub(crate) running: Time,
+    pub(crate) waiting: Time,
+    pub(crate) timeslices: u64,
+}
+
+impl SchedStat {
+    /// Returns logical CPU number these statistics belong to.
+    pub fn cpu(&self) -> u32 {
+        self.cpu
+    }
+
+    /// Returns time spent by tasks running on this CPU.
+    pub fn running(&self) -> Time {
+        self.running
+    }
+
+    /// Returns time spent by tasks waiting on the run queue of this CPU.
+    pub fn waiting(&self) -> Time {
+        self.waiting
+    }
+
+    /// Returns number of timeslices run on this CPU.
+    pub fn timeslices(&self) -> u64 {
+        self.timeslices
+    }
+
+    /// Returns average time spent by task waiting on the run queue
+    /// per one timeslice.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if no timeslices were run.
+    pub fn average_wait(&self) -> Option<Time> {
+        if self.timeslices > 0 {
+            let wait = self.waiting.get::<time::second>() / self.timeslices as f64;
+
+            Some(Time::new::<time::second>(wait))
+        } else {
+            None
+        }
+    }
+}
+
+impl ops::Sub<SchedStat> for SchedStat {
+    type Output = SchedStat;
+
+    fn sub(self, rhs: SchedStat) -> SchedStat {
+        SchedStat {
+            cpu: self.cpu,
+            running: self.running - rhs.running,
+            waiting: self.waiting - rhs.waiting,
+            timeslices: self.timeslices.wrapping_sub(rhs.timeslices),
+        }
+    }
+}
+
+/// Returns a stream over the per-CPU [scheduler statistics].
+///
+/// Statistics are read from the `/proc/schedstat` file.
+///
+/// ## Compatibility
+///
+/// Kernel should be compiled with `CONFIG_SCHEDSTATS` option enabled
+/// and should expose schedstat format version 10 or newer.
+///
+/// [scheduler statistics]: ./struct.SchedStat.html
+pub async fn schedstat() -> Result<impl Stream<Item = Result<SchedStat>>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::schedstat().await
+        } else {
+            Ok(stream::iter(vec![]))
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::ops;
+
+use heim_common::prelude::*;
+use heim_common::units::Time;
+
+#[cfg(target_os = "linux")]
+use crate::sys;
+
+/// Thermal throttling counters for one logical CPU.
+///
+/// Counters are cumulative since boot, subtract two samples
+/// in order to find out if CPU was throttled between them:
+///
+/// ```rust,no_run
+/// # use heim_cpu::os::linux::ThermalThrottle;
+/// # fn check(before: ThermalThrottle, after: ThermalThrottle) {
+/// let delta = after - before;
+/// if delta.is_throttled() {
+///     println!("CPU {} was throttled {} times", delta.cpu(), delta.core_throttle_count());
+/// }
+/// # }
+/// ```
+///
+/// Package counters are shared by all CPUs located in the same physical package,
+/// therefore each of these CPUs will report the same values.
+#[derive(Debug, Clone)]
+pub struct ThermalThrottle {
+    pub(crate) cpu: u32,
+    pub(crate) core_count: u64,
+    pub(crate) core_time: Option<Time>,
+    pub(crate) package_count: Option<u64>,
+    pub(crate) package_time: Option<Time>,
+}
+
+impl ThermalThrottle {
+    /// Returns logical CPU number these counters belong to.
+    pub fn cpu(&self) -> u32 {
+        self.cpu
+    }
+
+    /// Returns how many times CPU core was throttled due to high temperature.
+    pub fn core_throttle_count(&self) -> u64 {
+        self.core_count
+    }
+
+    /// Returns total time CPU core spent being throttled.
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.19, older versions always return `None`.
+    pub fn core_throttle_time(&self) -> Option<Time> {
+        self.core_time
+    }
+
+    /// Returns how many times CPU package was throttled due to high temperature.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if CPU does not support package thermal management.
+    pub fn package_throttle_count(&self) -> Option<u64> {
+        self.package_count
+    }
+
+    /// Returns total time CPU package spent being throttled.
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.19, older versions always return `None`.
+    pub fn package_throttle_time(&self) -> Option<Time> {
+        self.package_time
+    }
+
+    /// Returns `true` if either core or package throttling event happened.
+    ///
+    /// For the difference between two samples it tells
+    /// if CPU was throttled during the sampling window.
+    pub fn is_throttled(&self) -> bool {
+        self.core_count > 0 || matches!(self.package_count, Some(count) if count > 0)
+    }
+}
+
+impl ops::Sub<ThermalThrottle> for ThermalThrottle {
+    type Output = ThermalThrottle;
+
+    fn sub(self, rhs: ThermalThrottle) -> ThermalThrottle {
+        let time = |left: Option<Time>, right: Option<Time>| match (left, right) {
+            (Some(left), Some(right)) => Some(left - right),
+            _ => None,
+        };
+        let package_count = match (self.package_count, rhs.package_count) {
+            (Some(left), Some(right)) => Some(left.wrapping_sub(right)),
+            _ => None,
+        };
+
+        ThermalThrottle {
+            cpu: self.cpu,
+            core_count: self.core_count.wrapping_sub(rhs.core_count),
+            core_time: time(self.core_time, rhs.core_time),
+            package_count,
+            package_time: time(self.package_time, rhs.package_time),
+        }
+    }
+}
+
+/// Returns a stream over the per-CPU [thermal throttling] counters.
+///
+/// Stream is ordered by the logical CPU number.
+///
+/// ## Compatibility
+///
+/// Counters are exposed by the kernel for Intel CPUs only,
+/// for other CPUs stream will be empty.
+///
+/// [thermal throttling]: ./struct.ThermalThrottle.html
+pub fn thermal_throttling() -> impl Stream<Item = Result<ThermalThrottle>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::thermal_throttling()
+        } else {
+            stream::iter(vec![])
+        }
+    }
+}
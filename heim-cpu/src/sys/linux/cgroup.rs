@@ -0,0 +1,152 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use heim_common::prelude::*;
+use heim_common::sys::linux::cgroup::{self, source, Cgroup, LimitSource, Version};
+use heim_common::sys::linux::parse_cpu_list;
+use heim_runtime as rt;
+
+use crate::os::linux::ContainerCpu;
+
+// Parses `cpu.max` file contents, ex. `50000 100000` or `max 100000`.
+fn parse_cpu_max(contents: &str) -> Result<Option<f64>> {
+    let mut parts = contents.split_whitespace();
+    match (parts.next(), parts.next()) {
+        (Some("max"), _) => Ok(None),
+        (Some(quota), Some(period)) => quota_ratio(quota, period),
+        _ => {
+            let inner = io::Error::from(io::ErrorKind::InvalidData);
+            Err(Error::from(inner))
+        }
+    }
+}
+
+// cgroup v1 uses `-1` quota for "no limit"
+fn quota_ratio(quota: &str, period: &str) -> Result<Option<f64>> {
+    let quota = quota.trim().parse::<i64>()?;
+    let period = period.trim().parse::<i64>()?;
+
+    if quota > 0 && period > 0 {
+        Ok(Some(quota as f64 / period as f64))
+    } else {
+        Ok(None)
+    }
+}
+
+fn read_quota(dir: &Path, version: Version) -> Result<Option<(f64, PathBuf)>> {
+    let (path, quota) = match version {
+        Version::V1 => {
+            let path = dir.join("cpu.cfs_quota_us");
+            let quota = match fs::read_to_string(&path) {
+                Ok(quota) => quota,
+                Err(..) => return Ok(None),
+            };
+            let period_path = dir.join("cpu.cfs_period_us");
+            let period = fs::read_to_string(&period_path)
+                .map_err(|e| Error::from(e).with_file(period_path))?;
+
+            let quota = quota_ratio(&quota, &period).map_err(|e| e.with_file(&path))?;
+            (path, quota)
+        }
+        Version::V2 => {
+            let path = dir.join("cpu.max");
+            let quota = match fs::read_to_string(&path) {
+                Ok(contents) => parse_cpu_max(&contents).map_err(|e| e.with_file(&path))?,
+                Err(..) => return Ok(None),
+            };
+            (path, quota)
+        }
+    };
+
+    Ok(quota.map(|quota| (quota, path)))
+}
+
+fn quota(group: &Cgroup) -> Result<Option<(f64, PathBuf)>> {
+    let mut lowest: Option<(f64, PathBuf)> = None;
+    for dir in group.ancestors() {
+        if let Some((value, path)) = read_quota(dir, group.version())? {
+            match lowest {
+                Some((current, _)) if current <= value => {}
+                _ => lowest = Some((value, path)),
+            }
+        }
+    }
+
+    Ok(lowest)
+}
+
+fn cpus(group: &Cgroup) -> Result<Option<(Vec<u32>, PathBuf)>> {
+    // Effective cpuset is already restricted by all parent groups
+    let candidates: &[&str] = match group.version() {
+        Version::V1 => &["cpuset.effective_cpus", "cpuset.cpus"],
+        Version::V2 => &["cpuset.cpus.effective"],
+    };
+
+    for filename in candidates {
+        let path = group.path().join(filename);
+        if let Ok(contents) = fs::read_to_string(&path) {
+            let cpus = parse_cpu_list(&contents).map_err(|e| e.with_file(&path))?;
+            return Ok(Some((cpus, path)));
+        }
+    }
+
+    Ok(None)
+}
+
+pub async fn container_cpu() -> Result<ContainerCpu> {
+    let host_count = super::logical_count().await?;
+
+    rt::spawn_blocking(move || {
+        let procfs_root = rt::linux::procfs_root();
+
+        let mut cpu = ContainerCpu {
+            count: host_count,
+            count_source: LimitSource::Host,
+            quota: None,
+            quota_source: LimitSource::Host,
+            cpus: None,
+            cpus_source: LimitSource::Host,
+        };
+
+        if let Some(group) = cgroup::cgroup(procfs_root, "cpu")? {
+            if let Some((quota, path)) = quota(&group)? {
+                let count = quota.ceil() as u64;
+                if count < cpu.count {
+                    cpu.count = count;
+                    cpu.count_source = source(group.version(), path.clone());
+                }
+                cpu.quota = Some(quota);
+                cpu.quota_source = source(group.version(), path);
+            }
+        }
+
+        if let Some(group) = cgroup::cgroup(procfs_root, "cpuset")? {
+            if let Some((cpus, path)) = cpus(&group)? {
+                let count = cpus.len() as u64;
+                if count > 0 && count < cpu.count {
+                    cpu.count = count;
+                    cpu.count_source = source(group.version(), path.clone());
+                }
+                cpu.cpus = Some(cpus);
+                cpu.cpus_source = source(group.version(), path);
+            }
+        }
+
+        Ok(cpu)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_cpu_max, quota_ratio};
+
+    #[test]
+    fn test_quota() {
+        assert_eq!(parse_cpu_max("max 100000\n").unwrap(), None);
+        assert_eq!(parse_cpu_max("150000 100000\n").unwrap(), Some(1.5));
+        assert_eq!(quota_ratio("-1\n", "100000\n").unwrap(), None);
+        assert_eq!(quota_ratio("50000\n", "100000\n").unwrap(), Some(0.5));
+    }
+}
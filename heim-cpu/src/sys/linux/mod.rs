@@ -1,9 +1,15 @@
+mod cgroup;
 mod count;
 mod freq;
+mod schedstat;
 mod stats;
+mod throttle;
 mod times;
 
+pub use self::cgroup::*;
 pub use self::count::*;
 pub use self::freq::*;
+pub use self::schedstat::*;
 pub use self::stats::*;
+pub use self::throttle::*;
 pub use self::times::*;
@@ -0,0 +1,130 @@
+use std::io;
+
+use heim_common::prelude::*;
+use heim_common::units::{time, Time};
+use heim_runtime as rt;
+
+use crate::os::linux::SchedStat;
+
+// Format description: https://www.kernel.org/doc/html/latest/scheduler/sched-stats.html
+//
+// Per-CPU lines are starting with the `cpuN` prefix,
+// followed by the fields set which depends on the format version.
+// In all supported versions three last fields are
+// running time, run queue waiting time (both in nanoseconds) and timeslices count.
+fn fields_count(version: u32) -> Option<usize> {
+    match version {
+        // Versions 12 and 14, Linux 2.6.13 - 2.6.22
+        10..=14 => Some(12),
+        // Version 15 dropped three `sched_yield` counters with the CFS scheduler in Linux 2.6.23,
+        // per-CPU lines are unchanged in the later versions
+        15..=u32::MAX => Some(9),
+        _ => None,
+    }
+}
+
+fn parse_version(line: &str) -> Result<u32> {
+    match line.strip_prefix("version ") {
+        Some(version) => version.trim().parse::<u32>().map_err(Error::from),
+        None => Err(Error::missing_key(
+            "version",
+            format!("{}/schedstat", rt::linux::procfs_root().display()),
+        )),
+    }
+}
+
+fn parse_cpu(line: &str, fields: usize) -> Result<SchedStat> {
+    let mut parts = line.split_whitespace();
+    let cpu = match parts.next() {
+        Some(name) => name.trim_start_matches("cpu").parse::<u32>()?,
+        None => return Err(io::Error::from(io::ErrorKind::InvalidData).into()),
+    };
+    let values = parts
+        .map(|value| value.parse::<u64>())
+        .collect::<std::result::Result<Vec<u64>, _>>()?;
+
+    if values.len() != fields {
+        let inner = io::Error::from(io::ErrorKind::InvalidData);
+        return Err(Error::from(inner).with_message(format!(
+            "Expected {} fields for cpu{}, got {}",
+            fields,
+            cpu,
+            values.len()
+        )));
+    }
+
+    Ok(SchedStat {
+        cpu,
+        running: Time::new::<time::nanosecond>(values[fields - 3] as f64),
+        waiting: Time::new::<time::nanosecond>(values[fields - 2] as f64),
+        timeslices: values[fields - 1],
+    })
+}
+
+fn parse(contents: &str) -> Result<Vec<SchedStat>> {
+    let mut lines = contents.lines();
+    let version = parse_version(lines.next().unwrap_or_default())?;
+    let fields = fields_count(version).ok_or_else(|| {
+        let inner = io::Error::from(io::ErrorKind::InvalidData);
+        Error::from(inner).with_message(format!("Unsupported schedstat version {}", version))
+    })?;
+
+    lines
+        .filter(|line| line.starts_with("cpu"))
+        .map(|line| parse_cpu(line, fields))
+        .collect()
+}
+
+pub async fn schedstat() -> Result<impl Stream<Item = Result<SchedStat>>> {
+    let contents = rt::fs::read_to_string(rt::linux::procfs_root().join("schedstat")).await?;
+    let stats = parse(&contents)?;
+
+    Ok(stream::iter(stats.into_iter().map(Ok)))
+}
+
+#[cfg(test)]
+mod tests {
+    use heim_common::units::time;
+
+    use super::parse;
+
+    #[test]
+    fn test_version_14() {
+        const CONTENTS: &str = "version 14
+timestamp 4295031548
+cpu0 0 0 0 0 0 0 0 0 0 2000000 3000000 7
+domain0 3 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+";
+
+        let stats = parse(CONTENTS).unwrap();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].cpu(), 0);
+        assert_eq!(stats[0].running().get::<time::millisecond>(), 2.0);
+        assert_eq!(stats[0].waiting().get::<time::millisecond>(), 3.0);
+        assert_eq!(stats[0].timeslices(), 7);
+    }
+
+    #[test]
+    fn test_version_15() {
+        const CONTENTS: &str = "version 15
+timestamp 4295031548
+cpu0 0 0 1024 512 300 200 2000000 3000000 7
+domain0 03 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+cpu1 0 0 1024 512 300 200 4000000 1000000 2
+";
+
+        let stats = parse(CONTENTS).unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[1].cpu(), 1);
+        assert_eq!(stats[1].running().get::<time::millisecond>(), 4.0);
+        assert_eq!(
+            stats[1].average_wait().unwrap().get::<time::millisecond>(),
+            0.5
+        );
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        assert!(parse("version 9\ntimestamp 0\n").is_err());
+    }
+}
@@ -0,0 +1,66 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use heim_common::prelude::{Error, Result, Stream};
+use heim_common::units::{time, Time};
+use heim_runtime as rt;
+
+use crate::os::linux::ThermalThrottle;
+
+fn read_u64(path: &Path) -> Result<u64> {
+    let contents = fs::read_to_string(path)?;
+
+    contents.trim_end().parse::<u64>().map_err(Error::from)
+}
+
+fn read_ms(path: &Path) -> Option<Time> {
+    // Don't care about errors propagation at this point,
+    // these files are missing in older kernels
+    read_u64(path)
+        .ok()
+        .map(|value| Time::new::<time::millisecond>(value as f64))
+}
+
+fn cpu_number(path: &Path) -> Option<u32> {
+    path.parent()?
+        .file_name()?
+        .to_str()?
+        .trim_start_matches("cpu")
+        .parse()
+        .ok()
+}
+
+/// Internal blocking iterator over CPU thermal throttling counters.
+fn _thermal_throttling() -> impl Iterator<Item = Result<ThermalThrottle>> {
+    let path = rt::linux::sysfs_root().join("devices/system/cpu/cpu[0-9]*/thermal_throttle/");
+
+    let entries = glob::glob(path.display().to_string().as_str()).expect("Incorrect glob pattern");
+
+    let mut throttles = entries
+        .map(|try_path| {
+            let path = try_path.map_err(io::Error::from)?;
+            let cpu = cpu_number(&path).ok_or_else(|| {
+                Error::from(io::Error::from(io::ErrorKind::InvalidData)).with_file(&path)
+            })?;
+
+            Ok(ThermalThrottle {
+                cpu,
+                core_count: read_u64(&path.join("core_throttle_count"))?,
+                core_time: read_ms(&path.join("core_throttle_total_time_ms")),
+                package_count: read_u64(&path.join("package_throttle_count")).ok(),
+                package_time: read_ms(&path.join("package_throttle_total_time_ms")),
+            })
+        })
+        .collect::<Vec<Result<ThermalThrottle>>>();
+
+    // `glob` yields paths in the lexicographical order (`cpu1`, `cpu10`, `cpu2`),
+    // but we want to keep them ordered by the CPU number
+    throttles.sort_by_key(|throttle| throttle.as_ref().map(ThermalThrottle::cpu).ok());
+
+    throttles.into_iter()
+}
+
+pub fn thermal_throttling() -> impl Stream<Item = Result<ThermalThrottle>> {
+    smol::stream::iter(_thermal_throttling())
+}
@@ -29,6 +29,56 @@ async fn smoke_frequencies() {
     }
 }
 
+#[heim_derive::test]
+#[cfg(target_os = "linux")]
+async fn smoke_thermal_throttling() {
+    let throttling = cpu::os::linux::thermal_throttling();
+    futures::pin_mut!(throttling);
+    while let Some(throttle) = throttling.next().await {
+        let throttle = throttle.unwrap();
+
+        let _ = throttle.cpu();
+        let _ = throttle.core_throttle_count();
+        let _ = throttle.core_throttle_time();
+        let _ = throttle.package_throttle_count();
+        let _ = throttle.package_throttle_time();
+        let _ = throttle.is_throttled();
+    }
+}
+
+#[heim_derive::test]
+#[cfg(target_os = "linux")]
+async fn smoke_schedstat() {
+    // `/proc/schedstat` is missing if kernel was built without `CONFIG_SCHEDSTATS`
+    if let Ok(stats) = cpu::os::linux::schedstat().await {
+        futures::pin_mut!(stats);
+        while let Some(stat) = stats.next().await {
+            let stat = stat.unwrap();
+
+            let _ = stat.cpu();
+            let _ = stat.running();
+            let _ = stat.waiting();
+            let _ = stat.timeslices();
+            let _ = stat.average_wait();
+        }
+    }
+}
+
+#[heim_derive::test]
+#[cfg(target_os = "linux")]
+async fn smoke_container_cpu() {
+    let host = cpu::logical_count().await.unwrap();
+    let limits = cpu::os::linux::container_cpu().await.unwrap();
+
+    assert!(limits.count() > 0);
+    assert!(limits.count() <= host);
+    let _ = limits.count_source().path();
+    let _ = limits.quota();
+    let _ = limits.quota_source();
+    let _ = limits.cpus();
+    let _ = limits.cpus_source();
+}
+
 #[heim_derive::test]
 async fn smoke_stats() {
     let stats = cpu::stats().await.unwrap();
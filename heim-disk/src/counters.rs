@@ -2,7 +2,7 @@ use std::ffi::OsStr;
 use std::fmt;
 
 use heim_common::prelude::*;
-use heim_common::units::Information;
+use heim_common::units::{Information, Time};
 
 use crate::sys;
 
@@ -46,6 +46,33 @@ impl IoCounters {
     pub fn write_bytes(&self) -> Information {
         self.as_ref().write_bytes()
     }
+
+    /// Returns time spent doing I/O.
+    ///
+    /// ## Compatibility
+    ///
+    /// Available for Linux only, other platforms always return `None`.
+    pub fn busy_time(&self) -> Option<Time> {
+        self.as_ref().busy_time()
+    }
+
+    /// Returns number of reads merged with the adjacent ones.
+    ///
+    /// ## Compatibility
+    ///
+    /// Available for Linux only, other platforms always return `None`.
+    pub fn read_merged_count(&self) -> Option<u64> {
+        self.as_ref().read_merged_count()
+    }
+
+    /// Returns number of writes merged with the adjacent ones.
+    ///
+    /// ## Compatibility
+    ///
+    /// Available for Linux only, other platforms always return `None`.
+    pub fn write_merged_count(&self) -> Option<u64> {
+        self.as_ref().write_merged_count()
+    }
 }
 
 impl fmt::Debug for IoCounters {
@@ -56,6 +83,9 @@ impl fmt::Debug for IoCounters {
             .field("write_count", &self.write_count())
             .field("read_bytes", &self.read_bytes())
             .field("write_bytes", &self.write_bytes())
+            .field("busy_time", &self.busy_time())
+            .field("read_merged_count", &self.read_merged_count())
+            .field("write_merged_count", &self.write_merged_count())
             .finish()
     }
 }
@@ -4,8 +4,9 @@ use std::str::FromStr;
 
 /// Known filesystems.
 ///
-/// All physical filesystems should have their own enum element
-/// and all virtual filesystems will go into the `Other` element.
+/// All physical filesystems should have their own enum element,
+/// commonly used virtual, network and pseudo filesystems have them too;
+/// the rest will go into the `Other` element.
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
 #[non_exhaustive]
 pub enum FileSystem {
@@ -67,27 +68,192 @@ pub enum FileSystem {
     /// FUSE (https://en.wikipedia.org/wiki/Filesystem_in_Userspace)
     FuseBlk,
 
-    // TODO: Extend list
+    /// bcachefs (https://en.wikipedia.org/wiki/Bcachefs)
+    Bcachefs,
+
+    /// UDF (https://en.wikipedia.org/wiki/Universal_Disk_Format)
+    Udf,
+
+    /// ISO 9660 (https://en.wikipedia.org/wiki/ISO_9660)
+    Iso9660,
+
+    /// SquashFS (https://en.wikipedia.org/wiki/SquashFS)
+    SquashFs,
+
+    /// EROFS (https://en.wikipedia.org/wiki/EROFS)
+    EroFs,
+
+    /// tmpfs (https://en.wikipedia.org/wiki/Tmpfs)
+    Tmpfs,
+
+    /// ramfs, memory filesystem without size limit
+    Ramfs,
+
+    /// devtmpfs, tmpfs instance with the device nodes populated by kernel
+    Devtmpfs,
+
+    /// OverlayFS (https://en.wikipedia.org/wiki/OverlayFS)
+    Overlay,
+
+    /// FUSE filesystem without the block device and subtype (https://en.wikipedia.org/wiki/Filesystem_in_Userspace)
+    Fuse,
+
+    /// virtiofs, directory shared by the virtual machine host (https://virtio-fs.gitlab.io/)
+    VirtioFs,
+
+    /// NFS up to version 3 (https://en.wikipedia.org/wiki/Network_File_System)
+    Nfs,
+
+    /// NFS version 4 (https://en.wikipedia.org/wiki/Network_File_System)
+    Nfs4,
+
+    /// SMB mounted with Linux `cifs` driver (https://en.wikipedia.org/wiki/Server_Message_Block)
+    Cifs,
+
+    /// SMB 3 mounted with Linux `smb3` filesystem type (https://en.wikipedia.org/wiki/Server_Message_Block)
+    Smb3,
+
+    /// SMB mounted on macOS (https://en.wikipedia.org/wiki/Server_Message_Block)
+    SmbFs,
+
+    /// CephFS (https://en.wikipedia.org/wiki/Ceph_(software))
+    Ceph,
+
+    /// 9P (https://en.wikipedia.org/wiki/9P_(protocol))
+    NineP,
+
+    /// procfs (https://en.wikipedia.org/wiki/Procfs)
+    Proc,
+
+    /// sysfs (https://en.wikipedia.org/wiki/Sysfs)
+    Sysfs,
+
+    /// devpts, pseudo terminals filesystem
+    Devpts,
+
+    /// cgroup v1 hierarchy (https://en.wikipedia.org/wiki/Cgroups)
+    Cgroup,
+
+    /// cgroup v2 unified hierarchy (https://en.wikipedia.org/wiki/Cgroups)
+    Cgroup2,
+
     /// Some unspecified filesystem.
     Other(String),
 }
 
+// Filesystems without own enum variants, matched by their names
+const NETWORK_OTHER: &[&str] = &[
+    "afs",
+    "afpfs",
+    "glusterfs",
+    "lustre",
+    "ncpfs",
+    "webdav",
+    "fuse.glusterfs",
+    "fuse.rclone",
+    "fuse.s3fs",
+    "fuse.sshfs",
+];
+
+const PSEUDO_OTHER: &[&str] = &[
+    "autofs",
+    "binfmt_misc",
+    "bpf",
+    "configfs",
+    "debugfs",
+    "devfs",
+    "efivarfs",
+    "fusectl",
+    "hugetlbfs",
+    "mqueue",
+    "nsfs",
+    "pstore",
+    "rpc_pipefs",
+    "securityfs",
+    "selinuxfs",
+    "tracefs",
+];
+
 impl FileSystem {
     /// Checks if filesystem is used for a physical devices
-    // TODO: Revisit clippy lint later, `match!` should be in MSRV now?
-    #[allow(clippy::match_like_matches_macro)]
     pub fn is_physical(&self) -> bool {
-        match self {
-            FileSystem::Other(..) => false,
-            _ => true,
-        }
+        matches!(
+            self,
+            FileSystem::Ext2
+                | FileSystem::Ext3
+                | FileSystem::Ext4
+                | FileSystem::VFat
+                | FileSystem::ExFat
+                | FileSystem::F2fs
+                | FileSystem::Ntfs
+                | FileSystem::Zfs
+                | FileSystem::Hfs
+                | FileSystem::HfsPlus
+                | FileSystem::Jfs
+                | FileSystem::Reiser3
+                | FileSystem::Reiser4
+                | FileSystem::Btrfs
+                | FileSystem::Minix
+                | FileSystem::Nilfs
+                | FileSystem::Xfs
+                | FileSystem::Apfs
+                | FileSystem::FuseBlk
+                | FileSystem::Bcachefs
+                | FileSystem::Udf
+                | FileSystem::Iso9660
+                | FileSystem::SquashFs
+                | FileSystem::EroFs
+        )
     }
 
     /// Checks if filesystem is used for a virtual devices (such as `tmpfs` or `smb` mounts)
+    ///
+    /// This includes [network] and [pseudo] filesystems too.
+    ///
+    /// [network]: #method.is_network
+    /// [pseudo]: #method.is_pseudo
     pub fn is_virtual(&self) -> bool {
         !self.is_physical()
     }
 
+    /// Checks if filesystem data is accessed over the network, ex. NFS or SMB shares.
+    pub fn is_network(&self) -> bool {
+        match self {
+            FileSystem::Nfs
+            | FileSystem::Nfs4
+            | FileSystem::Cifs
+            | FileSystem::Smb3
+            | FileSystem::SmbFs
+            | FileSystem::Ceph
+            | FileSystem::NineP => true,
+            FileSystem::Other(name) => NETWORK_OTHER.contains(&name.as_str()),
+            _ => false,
+        }
+    }
+
+    /// Checks if filesystem is a read-only image format, ex. SquashFS used for snap packages
+    /// or ISO 9660 for optical discs.
+    pub fn is_read_only_image(&self) -> bool {
+        matches!(
+            self,
+            FileSystem::SquashFs | FileSystem::EroFs | FileSystem::Iso9660
+        )
+    }
+
+    /// Checks if filesystem is a kernel interface without any user data,
+    /// ex. `proc`, `sysfs` or `cgroup2`.
+    pub fn is_pseudo(&self) -> bool {
+        match self {
+            FileSystem::Proc
+            | FileSystem::Sysfs
+            | FileSystem::Devpts
+            | FileSystem::Cgroup
+            | FileSystem::Cgroup2 => true,
+            FileSystem::Other(name) => PSEUDO_OTHER.contains(&name.as_str()),
+            _ => false,
+        }
+    }
+
     /// Returns a string identifying this filesystem.
     pub fn as_str(&self) -> &str {
         match self {
@@ -110,6 +276,29 @@ impl FileSystem {
             FileSystem::Nilfs => "nilfs",
             FileSystem::Xfs => "xfs",
             FileSystem::Apfs => "apfs",
+            FileSystem::Bcachefs => "bcachefs",
+            FileSystem::Udf => "udf",
+            FileSystem::Iso9660 => "iso9660",
+            FileSystem::SquashFs => "squashfs",
+            FileSystem::EroFs => "erofs",
+            FileSystem::Tmpfs => "tmpfs",
+            FileSystem::Ramfs => "ramfs",
+            FileSystem::Devtmpfs => "devtmpfs",
+            FileSystem::Overlay => "overlay",
+            FileSystem::Fuse => "fuse",
+            FileSystem::VirtioFs => "virtiofs",
+            FileSystem::Nfs => "nfs",
+            FileSystem::Nfs4 => "nfs4",
+            FileSystem::Cifs => "cifs",
+            FileSystem::Smb3 => "smb3",
+            FileSystem::SmbFs => "smbfs",
+            FileSystem::Ceph => "ceph",
+            FileSystem::NineP => "9p",
+            FileSystem::Proc => "proc",
+            FileSystem::Sysfs => "sysfs",
+            FileSystem::Devpts => "devpts",
+            FileSystem::Cgroup => "cgroup",
+            FileSystem::Cgroup2 => "cgroup2",
             FileSystem::Other(string) => string.as_str(),
         }
     }
@@ -138,9 +327,68 @@ impl FromStr for FileSystem {
             _ if s.eq_ignore_ascii_case("nilfs") => Ok(FileSystem::Nilfs),
             _ if s.eq_ignore_ascii_case("xfs") => Ok(FileSystem::Xfs),
             _ if s.eq_ignore_ascii_case("apfs") => Ok(FileSystem::Apfs),
+            _ if s.eq_ignore_ascii_case("bcachefs") => Ok(FileSystem::Bcachefs),
+            _ if s.eq_ignore_ascii_case("udf") => Ok(FileSystem::Udf),
+            _ if s.eq_ignore_ascii_case("iso9660") => Ok(FileSystem::Iso9660),
+            _ if s.eq_ignore_ascii_case("squashfs") => Ok(FileSystem::SquashFs),
+            _ if s.eq_ignore_ascii_case("erofs") => Ok(FileSystem::EroFs),
+
+            _ if s.eq_ignore_ascii_case("tmpfs") => Ok(FileSystem::Tmpfs),
+            _ if s.eq_ignore_ascii_case("ramfs") => Ok(FileSystem::Ramfs),
+            _ if s.eq_ignore_ascii_case("devtmpfs") => Ok(FileSystem::Devtmpfs),
+            _ if s.eq_ignore_ascii_case("overlay") => Ok(FileSystem::Overlay),
+            _ if s.eq_ignore_ascii_case("fuse") => Ok(FileSystem::Fuse),
+            _ if s.eq_ignore_ascii_case("virtiofs") => Ok(FileSystem::VirtioFs),
+
+            _ if s.eq_ignore_ascii_case("nfs") => Ok(FileSystem::Nfs),
+            _ if s.eq_ignore_ascii_case("nfs4") => Ok(FileSystem::Nfs4),
+            _ if s.eq_ignore_ascii_case("cifs") => Ok(FileSystem::Cifs),
+            _ if s.eq_ignore_ascii_case("smb3") => Ok(FileSystem::Smb3),
+            _ if s.eq_ignore_ascii_case("smbfs") => Ok(FileSystem::SmbFs),
+            _ if s.eq_ignore_ascii_case("ceph") => Ok(FileSystem::Ceph),
+            _ if s.eq_ignore_ascii_case("9p") => Ok(FileSystem::NineP),
+
+            _ if s.eq_ignore_ascii_case("proc") => Ok(FileSystem::Proc),
+            _ if s.eq_ignore_ascii_case("sysfs") => Ok(FileSystem::Sysfs),
+            _ if s.eq_ignore_ascii_case("devpts") => Ok(FileSystem::Devpts),
+            _ if s.eq_ignore_ascii_case("cgroup") => Ok(FileSystem::Cgroup),
+            _ if s.eq_ignore_ascii_case("cgroup2") => Ok(FileSystem::Cgroup2),
 
             _ if s.eq_ignore_ascii_case("fuseblk") => Ok(FileSystem::FuseBlk),
             _ => Ok(FileSystem::Other(s.to_string())),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::FileSystem;
+
+    #[test]
+    fn test_from_str_roundtrip() {
+        for name in &[
+            "tmpfs", "overlay", "nfs4", "smb3", "ceph", "9p", "squashfs", "cgroup2",
+        ] {
+            let fs = FileSystem::from_str(name).unwrap();
+            assert!(!matches!(fs, FileSystem::Other(..)), "{}", name);
+            assert_eq!(fs.as_str(), *name);
+        }
+    }
+
+    #[test]
+    fn test_classification() {
+        assert!(FileSystem::Ext4.is_physical());
+        assert!(FileSystem::SquashFs.is_physical());
+        assert!(FileSystem::SquashFs.is_read_only_image());
+        assert!(FileSystem::Tmpfs.is_virtual());
+        assert!(!FileSystem::Tmpfs.is_pseudo());
+        assert!(FileSystem::Nfs.is_network());
+        assert!(FileSystem::Nfs.is_virtual());
+        assert!(FileSystem::Other("fuse.sshfs".to_string()).is_network());
+        assert!(FileSystem::Proc.is_pseudo());
+        assert!(FileSystem::Other("tracefs".to_string()).is_pseudo());
+        assert!(!FileSystem::Other("unknown".to_string()).is_pseudo());
+    }
+}
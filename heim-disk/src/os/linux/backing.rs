@@ -0,0 +1,91 @@
+use heim_common::prelude::*;
+
+#[cfg(target_os = "linux")]
+use super::PartitionExt;
+#[cfg(target_os = "linux")]
+use crate::sys;
+use crate::Partition;
+
+/// Block device backing the mounted [Partition].
+///
+/// [Partition]: ../../struct.Partition.html
+#[derive(Debug, Clone)]
+pub struct BackingDevice {
+    pub(crate) major: u32,
+    pub(crate) minor: u32,
+    pub(crate) name: Option<String>,
+    pub(crate) disks: Vec<String>,
+}
+
+impl BackingDevice {
+    /// Returns device major number.
+    pub fn major(&self) -> u32 {
+        self.major
+    }
+
+    /// Returns device minor number.
+    pub fn minor(&self) -> u32 {
+        self.minor
+    }
+
+    /// Returns block device name, ex. `sda1`, `nvme0n1p2` or `dm-0`.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if partition is not backed by a block device,
+    /// ex. for `tmpfs` or network filesystems.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns names of the whole disks this device resides on,
+    /// tracing it through partitions and device-mapper or md devices.
+    ///
+    /// These names are used in the `/proc/diskstats` file
+    /// and can be matched with the [`IoCounters::device_name`].
+    ///
+    /// [`IoCounters::device_name`]: ../../struct.IoCounters.html#method.device_name
+    pub fn disks(&self) -> &[String] {
+        &self.disks
+    }
+}
+
+/// Returns [block device] which backs the `partition`.
+///
+/// For overlay mounts, device of the upper (writable) layer directory is returned,
+/// unless that directory is not visible, ex. from inside of the container;
+/// overlay mount own device numbers are returned in that case.
+///
+/// ```rust,no_run
+/// # use heim_common::prelude::*;
+/// # use heim_disk::os::linux::backing_device;
+/// #
+/// # #[heim_derive::main]
+/// # async fn main() -> Result<()> {
+/// let partition = heim_disk::mount_for_path("/var/lib").await?;
+/// let device = backing_device(&partition).await?;
+///
+/// let counters = heim_disk::io_counters().await?;
+/// heim_runtime::pin!(counters);
+/// while let Some(counters) = counters.next().await {
+///     let counters = counters?;
+///     if device.disks().iter().any(|disk| counters.device_name() == disk.as_str()) {
+///         println!("{:?}", counters);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [block device]: ./struct.BackingDevice.html
+pub async fn backing_device(partition: &Partition) -> Result<BackingDevice> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::backing_device(partition.mount_info().clone()).await
+        } else {
+            let _ = partition;
+
+            unimplemented!("For documentation rendering")
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use heim_common::prelude::*;
+use heim_common::units::Information;
+
+#[cfg(target_os = "linux")]
+use crate::sys;
+
+use super::FsIdentity;
+
+/// Partition of the [block device].
+///
+/// [block device]: ./struct.BlockDevice.html
+#[derive(Debug, Clone)]
+pub struct BlockPartition {
+    pub(crate) name: String,
+    pub(crate) major: u32,
+    pub(crate) minor: u32,
+    pub(crate) number: Option<u32>,
+    pub(crate) start: Option<Information>,
+    pub(crate) size: Information,
+    pub(crate) read_only: bool,
+    pub(crate) holders: Vec<String>,
+    pub(crate) identity: Option<FsIdentity>,
+}
+
+impl BlockPartition {
+    /// Returns partition name, ex. `sda1` or `nvme0n1p2`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns partition device major number.
+    pub fn major(&self) -> u32 {
+        self.major
+    }
+
+    /// Returns partition device minor number.
+    pub fn minor(&self) -> u32 {
+        self.minor
+    }
+
+    /// Returns partition number in the partition table.
+    pub fn number(&self) -> Option<u32> {
+        self.number
+    }
+
+    /// Returns partition offset from the beginning of the disk.
+    pub fn start(&self) -> Option<Information> {
+        self.start
+    }
+
+    /// Returns partition size.
+    pub fn size(&self) -> Information {
+        self.size
+    }
+
+    /// Returns `true` if partition is read-only.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Returns names of the devices built on top of this partition,
+    /// ex. `dm-0` for LVM physical volume or `md0` for RAID member.
+    pub fn holders(&self) -> &[String] {
+        &self.holders
+    }
+
+    /// Returns filesystem and partition identifiers.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if partition is missing in the `/dev/disk/by-*` directories.
+    pub fn identity(&self) -> Option<&FsIdentity> {
+        self.identity.as_ref()
+    }
+}
+
+/// Block device, as seen in the `/sys/block` directory.
+///
+/// Device name matches the one used in the `/proc/diskstats` file,
+/// so it can be used to find corresponding [IoCounters].
+///
+/// [IoCounters]: ../../struct.IoCounters.html
+#[derive(Debug, Clone)]
+pub struct BlockDevice {
+    pub(crate) name: String,
+    pub(crate) major: u32,
+    pub(crate) minor: u32,
+    pub(crate) size: Information,
+    pub(crate) logical_block_size: Option<Information>,
+    pub(crate) physical_block_size: Option<Information>,
+    pub(crate) hw_sector_size: Option<Information>,
+    pub(crate) rotational: Option<bool>,
+    pub(crate) removable: bool,
+    pub(crate) read_only: bool,
+    pub(crate) model: Option<String>,
+    pub(crate) vendor: Option<String>,
+    pub(crate) serial: Option<String>,
+    pub(crate) wwn: Option<String>,
+    pub(crate) scheduler: Option<String>,
+    pub(crate) queue_depth: Option<u32>,
+    pub(crate) nr_requests: Option<u32>,
+    pub(crate) partitions: Vec<BlockPartition>,
+    pub(crate) holders: Vec<String>,
+    pub(crate) slaves: Vec<String>,
+    pub(crate) backing_disks: Vec<String>,
+    pub(crate) identity: Option<FsIdentity>,
+}
+
+impl BlockDevice {
+    /// Returns device name, ex. `sda`, `nvme0n1` or `dm-0`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns device major number.
+    pub fn major(&self) -> u32 {
+        self.major
+    }
+
+    /// Returns device minor number.
+    pub fn minor(&self) -> u32 {
+        self.minor
+    }
+
+    /// Returns device size.
+    pub fn size(&self) -> Information {
+        self.size
+    }
+
+    /// Returns the smallest unit device is able to address.
+    pub fn logical_block_size(&self) -> Option<Information> {
+        self.logical_block_size
+    }
+
+    /// Returns the smallest unit device is able to write atomically.
+    pub fn physical_block_size(&self) -> Option<Information> {
+        self.physical_block_size
+    }
+
+    /// Returns hardware sector size, which is usually the same as the [logical block size].
+    ///
+    /// Note that it is not related to the sector units used in `/proc/diskstats`
+    /// and `/sys/block/*/size` files, which are always 512 bytes long.
+    ///
+    /// [logical block size]: #method.logical_block_size
+    pub fn hw_sector_size(&self) -> Option<Information> {
+        self.hw_sector_size
+    }
+
+    /// Returns `true` if device is a rotational drive (HDD) and `false` for SSD-like devices.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if device does not have a request queue.
+    pub fn is_rotational(&self) -> Option<bool> {
+        self.rotational
+    }
+
+    /// Returns `true` if device media is removable, ex. for CD-ROMs and card readers.
+    pub fn is_removable(&self) -> bool {
+        self.removable
+    }
+
+    /// Returns `true` if device is read-only.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Returns device model, if reported by the driver.
+    pub fn model(&self) -> Option<&str> {
+        self.model.as_deref()
+    }
+
+    /// Returns device vendor, if reported by the driver.
+    pub fn vendor(&self) -> Option<&str> {
+        self.vendor.as_deref()
+    }
+
+    /// Returns device serial number, if reported by the driver.
+    pub fn serial(&self) -> Option<&str> {
+        self.serial.as_deref()
+    }
+
+    /// Returns device World Wide Name (or NVMe namespace identifier),
+    /// if reported by the driver.
+    pub fn wwn(&self) -> Option<&str> {
+        self.wwn.as_deref()
+    }
+
+    /// Returns active I/O scheduler name, ex. `mq-deadline` or `none`.
+    pub fn scheduler(&self) -> Option<&str> {
+        self.scheduler.as_deref()
+    }
+
+    /// Returns number of commands device can have queued at once.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` for non-SCSI devices.
+    pub fn queue_depth(&self) -> Option<u32> {
+        self.queue_depth
+    }
+
+    /// Returns number of requests which can be allocated in the block layer queue.
+    pub fn nr_requests(&self) -> Option<u32> {
+        self.nr_requests
+    }
+
+    /// Returns device partitions.
+    pub fn partitions(&self) -> &[BlockPartition] {
+        &self.partitions
+    }
+
+    /// Returns names of the devices built on top of this device,
+    /// ex. device-mapper or md devices.
+    pub fn holders(&self) -> &[String] {
+        &self.holders
+    }
+
+    /// Returns names of the devices this one is built on top of,
+    /// ex. LVM physical volumes for the `dm-*` device.
+    ///
+    /// These can be either whole disks or partitions.
+    pub fn slaves(&self) -> &[String] {
+        &self.slaves
+    }
+
+    /// Returns names of the whole disks at the bottom of the slaves chain,
+    /// ex. `sda` and `sdb` for the LVM volume on top of the RAID1 over `sda1` and `sdb1`.
+    ///
+    /// Empty for devices which are not built on top of other devices.
+    pub fn backing_disks(&self) -> &[String] {
+        &self.backing_disks
+    }
+
+    /// Returns identifiers of the filesystem created directly on the device,
+    /// without the partition table.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if device is missing in the `/dev/disk/by-*` directories.
+    pub fn identity(&self) -> Option<&FsIdentity> {
+        self.identity.as_ref()
+    }
+}
+
+/// Returns a stream over [block devices] present in the system.
+///
+/// Partitions are not included in the stream, see [`BlockDevice::partitions`] instead.
+///
+/// [block devices]: ./struct.BlockDevice.html
+/// [`BlockDevice::partitions`]: ./struct.BlockDevice.html#method.partitions
+pub async fn block_devices() -> Result<impl Stream<Item = Result<BlockDevice>>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::block_devices().await
+        } else {
+            Ok(stream::iter(vec![]))
+        }
+    }
+}
@@ -0,0 +1,316 @@
+use heim_common::prelude::*;
+use heim_common::units::Information;
+
+#[cfg(target_os = "linux")]
+use super::PartitionExt;
+#[cfg(target_os = "linux")]
+use crate::sys;
+use crate::Partition;
+
+/// Btrfs block group RAID profile.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum BtrfsProfile {
+    /// Single copy of data.
+    Single,
+    /// Two copies of data on the same device.
+    Dup,
+    /// Striping without redundancy.
+    Raid0,
+    /// Two copies of data on different devices.
+    Raid1,
+    /// Three copies of data on different devices.
+    Raid1C3,
+    /// Four copies of data on different devices.
+    Raid1C4,
+    /// Striping over the mirrored pairs.
+    Raid10,
+    /// Striping with single parity.
+    Raid5,
+    /// Striping with double parity.
+    Raid6,
+    /// Some other profile, contains the sysfs directory name.
+    Other(String),
+}
+
+/// Space allocated for one [RAID profile] of the btrfs block group type.
+///
+/// [RAID profile]: ./enum.BtrfsProfile.html
+#[derive(Debug, Clone)]
+pub struct BtrfsProfileUsage {
+    pub(crate) profile: BtrfsProfile,
+    pub(crate) total: Information,
+    pub(crate) used: Information,
+}
+
+impl BtrfsProfileUsage {
+    /// Returns RAID profile.
+    pub fn profile(&self) -> &BtrfsProfile {
+        &self.profile
+    }
+
+    /// Returns logical space allocated for the block groups with this profile.
+    pub fn total(&self) -> Information {
+        self.total
+    }
+
+    /// Returns logical space used in the block groups with this profile.
+    pub fn used(&self) -> Information {
+        self.used
+    }
+}
+
+/// Space allocation for one of the btrfs block group types:
+/// data, metadata or system.
+///
+/// Values are read from the `/sys/fs/btrfs/<uuid>/allocation/<type>/` directory
+/// and are the same as reported by the `btrfs filesystem df` command.
+#[derive(Debug, Clone)]
+pub struct BtrfsAllocation {
+    pub(crate) total: Information,
+    pub(crate) used: Information,
+    pub(crate) disk_total: Option<Information>,
+    pub(crate) disk_used: Option<Information>,
+    pub(crate) profiles: Vec<BtrfsProfileUsage>,
+}
+
+impl BtrfsAllocation {
+    /// Returns logical space allocated for the block groups.
+    pub fn total(&self) -> Information {
+        self.total
+    }
+
+    /// Returns logical space used in the allocated block groups.
+    pub fn used(&self) -> Information {
+        self.used
+    }
+
+    /// Returns raw disk space allocated for the block groups,
+    /// ex. twice as large as [`total`] for the RAID1 profile.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if value is not provided by the kernel.
+    ///
+    /// [`total`]: #method.total
+    pub fn disk_total(&self) -> Option<Information> {
+        self.disk_total
+    }
+
+    /// Returns raw disk space used in the allocated block groups.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if value is not provided by the kernel.
+    pub fn disk_used(&self) -> Option<Information> {
+        self.disk_used
+    }
+
+    /// Returns allocation for each RAID profile used.
+    ///
+    /// Filesystem might have several profiles at once, ex. during the balance conversion.
+    pub fn profiles(&self) -> &[BtrfsProfileUsage] {
+        &self.profiles
+    }
+}
+
+/// Btrfs device error counters, same as reported by the `btrfs device stats` command.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BtrfsDeviceErrors {
+    pub(crate) write: u64,
+    pub(crate) read: u64,
+    pub(crate) flush: u64,
+    pub(crate) corruption: u64,
+    pub(crate) generation: u64,
+}
+
+impl BtrfsDeviceErrors {
+    /// Returns number of failed write requests.
+    pub fn write(&self) -> u64 {
+        self.write
+    }
+
+    /// Returns number of failed read requests.
+    pub fn read(&self) -> u64 {
+        self.read
+    }
+
+    /// Returns number of failed flush requests.
+    pub fn flush(&self) -> u64 {
+        self.flush
+    }
+
+    /// Returns number of checksum mismatches.
+    pub fn corruption(&self) -> u64 {
+        self.corruption
+    }
+
+    /// Returns number of blocks with unexpected generation, ex. lost writes.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+/// Btrfs member device state from the `/sys/fs/btrfs/<uuid>/devinfo/<devid>/` directory.
+///
+/// Kernel does not expose block device name there,
+/// see [`BtrfsFilesystem::devices`] for member device names.
+///
+/// [`BtrfsFilesystem::devices`]: ./struct.BtrfsFilesystem.html#method.devices
+#[derive(Debug, Clone)]
+pub struct BtrfsDeviceInfo {
+    pub(crate) devid: u64,
+    pub(crate) missing: bool,
+    pub(crate) writeable: bool,
+    pub(crate) replace_target: bool,
+    pub(crate) errors: Option<BtrfsDeviceErrors>,
+}
+
+impl BtrfsDeviceInfo {
+    /// Returns device ID within the filesystem.
+    pub fn devid(&self) -> u64 {
+        self.devid
+    }
+
+    /// Returns `true` if device is missing, ex. for the degraded mount.
+    pub fn is_missing(&self) -> bool {
+        self.missing
+    }
+
+    /// Returns `true` if device is writable.
+    pub fn is_writeable(&self) -> bool {
+        self.writeable
+    }
+
+    /// Returns `true` if device is a target of the running `btrfs replace` operation.
+    pub fn is_replace_target(&self) -> bool {
+        self.replace_target
+    }
+
+    /// Returns device error counters.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` for Linux kernels older than 5.14.
+    pub fn errors(&self) -> Option<&BtrfsDeviceErrors> {
+        self.errors.as_ref()
+    }
+}
+
+/// Mounted btrfs filesystem details.
+///
+/// Values are read from the `/sys/fs/btrfs/<uuid>/` directory.
+/// Unlike the `statvfs` numbers, [allocation] values take RAID profiles
+/// and separate metadata space into account.
+///
+/// [allocation]: #method.data
+#[derive(Debug, Clone)]
+pub struct BtrfsFilesystem {
+    pub(crate) uuid: String,
+    pub(crate) label: Option<String>,
+    pub(crate) node_size: Option<Information>,
+    pub(crate) sector_size: Option<Information>,
+    pub(crate) devices: Vec<String>,
+    pub(crate) device_infos: Vec<BtrfsDeviceInfo>,
+    pub(crate) data: Option<BtrfsAllocation>,
+    pub(crate) metadata: Option<BtrfsAllocation>,
+    pub(crate) system: Option<BtrfsAllocation>,
+}
+
+impl BtrfsFilesystem {
+    /// Returns filesystem UUID, which matches the [`FsIdentity::uuid`] value.
+    ///
+    /// [`FsIdentity::uuid`]: ./struct.FsIdentity.html#method.uuid
+    pub fn uuid(&self) -> &str {
+        &self.uuid
+    }
+
+    /// Returns filesystem label.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Returns metadata tree node size.
+    pub fn node_size(&self) -> Option<Information> {
+        self.node_size
+    }
+
+    /// Returns the smallest allocation unit.
+    pub fn sector_size(&self) -> Option<Information> {
+        self.sector_size
+    }
+
+    /// Returns member block device names, ex. `sda1` or `dm-0`.
+    pub fn devices(&self) -> &[String] {
+        &self.devices
+    }
+
+    /// Returns member devices state and error counters.
+    ///
+    /// Empty for Linux kernels older than 5.6.
+    pub fn device_infos(&self) -> &[BtrfsDeviceInfo] {
+        &self.device_infos
+    }
+
+    /// Returns space allocation for the file data.
+    ///
+    /// For filesystems with mixed block groups, this includes metadata too.
+    pub fn data(&self) -> Option<&BtrfsAllocation> {
+        self.data.as_ref()
+    }
+
+    /// Returns space allocation for the metadata.
+    pub fn metadata(&self) -> Option<&BtrfsAllocation> {
+        self.metadata.as_ref()
+    }
+
+    /// Returns space allocation for the system chunks.
+    pub fn system(&self) -> Option<&BtrfsAllocation> {
+        self.system.as_ref()
+    }
+}
+
+/// Returns a stream over mounted [btrfs filesystems].
+///
+/// [btrfs filesystems]: ./struct.BtrfsFilesystem.html
+pub async fn btrfs_filesystems() -> Result<impl Stream<Item = Result<BtrfsFilesystem>>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::btrfs_filesystems().await
+        } else {
+            Ok(stream::iter(vec![]))
+        }
+    }
+}
+
+/// Returns [btrfs filesystem] details for the `partition`.
+///
+/// Filesystem is found by the partition [UUID] first and by the mount source device
+/// if UUID is not available, ex. when `/dev/disk` directory is missing in containers.
+///
+/// ## Returns
+///
+/// Returns `None` if partition is not a btrfs filesystem.
+///
+/// [btrfs filesystem]: ./struct.BtrfsFilesystem.html
+/// [UUID]: ./fn.fs_identity.html
+pub async fn btrfs_filesystem(partition: &Partition) -> Result<Option<BtrfsFilesystem>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            if *partition.file_system() != crate::FileSystem::Btrfs {
+                return Ok(None);
+            }
+
+            let info = partition.mount_info().clone();
+            let uuid = super::fs_identity(partition)
+                .await?
+                .and_then(|identity| identity.uuid);
+
+            sys::btrfs_filesystem(info, uuid).await
+        } else {
+            let _ = partition;
+
+            unimplemented!("For documentation rendering")
+        }
+    }
+}
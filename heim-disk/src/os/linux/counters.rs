@@ -0,0 +1,123 @@
+use heim_common::units::{Information, Time};
+
+use super::DmDevice;
+
+/// Linux-specific extension to [IoCounters].
+///
+/// Values are read from the `/proc/diskstats` file,
+/// see `Documentation/admin-guide/iostats.rst` in the kernel source for details.
+///
+/// [IoCounters]: ../../struct.IoCounters.html
+pub trait IoCountersExt {
+    /// Returns time spent by all reads.
+    fn read_time(&self) -> Time;
+
+    /// Returns time spent by all writes.
+    fn write_time(&self) -> Time;
+
+    /// Returns number of I/O requests currently in progress.
+    fn io_in_progress(&self) -> u64;
+
+    /// Returns weighted time spent doing I/O:
+    /// each I/O request is counted with the number of requests in progress at the same time.
+    ///
+    /// Can be used to calculate average queue size.
+    fn weighted_io_time(&self) -> Time;
+
+    /// Returns number of completed discard requests.
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.18, older versions always return `None`.
+    fn discard_count(&self) -> Option<u64>;
+
+    /// Returns number of discard requests merged with the adjacent ones.
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.18, older versions always return `None`.
+    fn discard_merged_count(&self) -> Option<u64>;
+
+    /// Returns amount of discarded information.
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.18, older versions always return `None`.
+    fn discard_bytes(&self) -> Option<Information>;
+
+    /// Returns time spent by all discard requests.
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.18, older versions always return `None`.
+    fn discard_time(&self) -> Option<Time>;
+
+    /// Returns number of completed flush requests.
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 5.5, older versions always return `None`.
+    fn flush_count(&self) -> Option<u64>;
+
+    /// Returns time spent by all flush requests.
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 5.5, older versions always return `None`.
+    fn flush_time(&self) -> Option<Time>;
+
+    /// Returns device-mapper details for the `dm-*` devices,
+    /// such as the LVM logical volume name.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if device is not a device-mapper one.
+    fn dm_device(&self) -> Option<&DmDevice>;
+}
+
+#[cfg(target_os = "linux")]
+impl IoCountersExt for crate::IoCounters {
+    fn read_time(&self) -> Time {
+        self.as_ref().read_time()
+    }
+
+    fn write_time(&self) -> Time {
+        self.as_ref().write_time()
+    }
+
+    fn io_in_progress(&self) -> u64 {
+        self.as_ref().io_in_progress()
+    }
+
+    fn weighted_io_time(&self) -> Time {
+        self.as_ref().weighted_io_time()
+    }
+
+    fn discard_count(&self) -> Option<u64> {
+        self.as_ref().discard_count()
+    }
+
+    fn discard_merged_count(&self) -> Option<u64> {
+        self.as_ref().discard_merged_count()
+    }
+
+    fn discard_bytes(&self) -> Option<Information> {
+        self.as_ref().discard_bytes()
+    }
+
+    fn discard_time(&self) -> Option<Time> {
+        self.as_ref().discard_time()
+    }
+
+    fn flush_count(&self) -> Option<u64> {
+        self.as_ref().flush_count()
+    }
+
+    fn flush_time(&self) -> Option<Time> {
+        self.as_ref().flush_time()
+    }
+
+    fn dm_device(&self) -> Option<&DmDevice> {
+        self.as_ref().dm_device()
+    }
+}
@@ -0,0 +1,104 @@
+use std::path::{Path, PathBuf};
+
+use heim_common::prelude::*;
+
+#[cfg(target_os = "linux")]
+use super::PartitionExt;
+#[cfg(target_os = "linux")]
+use crate::sys;
+use crate::Partition;
+
+/// Device-mapper target kind, detected by the device UUID prefix.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum DmKind {
+    /// LVM logical volume (`LVM-` prefix).
+    Lvm,
+    /// dm-crypt or dm-verity device set up with `cryptsetup` (`CRYPT-` prefix).
+    Crypt,
+    /// Multipath device (`mpath-` prefix).
+    Multipath,
+    /// Partition mapping created by `kpartx` (`part` prefix).
+    Partition,
+    /// Device with some other UUID prefix, contains prefix up to the first `-`.
+    Other(String),
+}
+
+/// Device-mapper device details.
+///
+/// Values are read from the `/sys/block/dm-*/dm/` directory.
+#[derive(Debug, Clone)]
+pub struct DmDevice {
+    pub(crate) device_name: String,
+    pub(crate) name: String,
+    pub(crate) uuid: Option<String>,
+    pub(crate) kind: Option<DmKind>,
+    pub(crate) mapper_path: PathBuf,
+    pub(crate) lvm_names: Option<(String, String)>,
+}
+
+impl DmDevice {
+    /// Returns kernel device name, ex. `dm-3`,
+    /// which is used in the `/proc/diskstats` file.
+    pub fn device_name(&self) -> &str {
+        &self.device_name
+    }
+
+    /// Returns device-mapper name, ex. `vg0-root`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns device path in the `/dev/mapper` directory, ex. `/dev/mapper/vg0-root`.
+    pub fn mapper_path(&self) -> &Path {
+        &self.mapper_path
+    }
+
+    /// Returns device UUID, ex. `LVM-<vg uuid><lv uuid>`.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if device was created without UUID.
+    pub fn uuid(&self) -> Option<&str> {
+        self.uuid.as_deref()
+    }
+
+    /// Returns device kind, detected by the UUID prefix.
+    pub fn kind(&self) -> Option<&DmKind> {
+        self.kind.as_ref()
+    }
+
+    /// Returns LVM volume group and logical volume names.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if device is not an LVM logical volume.
+    pub fn lvm_names(&self) -> Option<(&str, &str)> {
+        self.lvm_names
+            .as_ref()
+            .map(|(vg, lv)| (vg.as_str(), lv.as_str()))
+    }
+}
+
+/// Returns device-mapper details if the `partition` is mounted from the device-mapper device,
+/// ex. `/dev/mapper/vg0-root`.
+///
+/// [`DmDevice::device_name`] can be used to find the corresponding `dm-*` [IoCounters].
+///
+/// ## Returns
+///
+/// Returns `None` if partition is not backed by a device-mapper device.
+///
+/// [`DmDevice::device_name`]: ./struct.DmDevice.html#method.device_name
+/// [IoCounters]: ../../struct.IoCounters.html
+pub async fn dm_device(partition: &Partition) -> Result<Option<DmDevice>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::mount_dm_device(partition.mount_info().clone()).await
+        } else {
+            let _ = partition;
+
+            Ok(None)
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use heim_common::prelude::*;
+
+#[cfg(target_os = "linux")]
+use super::PartitionExt;
+#[cfg(target_os = "linux")]
+use crate::sys;
+use crate::Partition;
+
+/// Filesystem and partition identifiers of the block device.
+///
+/// Values are resolved from the `/dev/disk/by-{uuid,label,partuuid,partlabel}`
+/// symbolic links maintained by udev; devfs root can be changed with
+/// `heim_runtime::linux::set_devfs_root`.
+#[derive(Debug, Clone, Default)]
+pub struct FsIdentity {
+    pub(crate) device_name: String,
+    pub(crate) uuid: Option<String>,
+    pub(crate) label: Option<String>,
+    pub(crate) part_uuid: Option<String>,
+    pub(crate) part_label: Option<String>,
+}
+
+impl FsIdentity {
+    /// Returns block device name, ex. `sda1` or `dm-0`.
+    pub fn device_name(&self) -> &str {
+        &self.device_name
+    }
+
+    /// Returns filesystem UUID.
+    pub fn uuid(&self) -> Option<&str> {
+        self.uuid.as_deref()
+    }
+
+    /// Returns filesystem label.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Returns GPT partition UUID or MBR disk signature with the partition number.
+    pub fn part_uuid(&self) -> Option<&str> {
+        self.part_uuid.as_deref()
+    }
+
+    /// Returns GPT partition name.
+    pub fn part_label(&self) -> Option<&str> {
+        self.part_label.as_deref()
+    }
+}
+
+/// Returns a stream over [identifiers] of all block devices known to udev.
+///
+/// Stream is empty if `/dev/disk` directory is not available, ex. in containers.
+///
+/// [identifiers]: ./struct.FsIdentity.html
+pub async fn fs_identities() -> Result<impl Stream<Item = Result<FsIdentity>>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::fs_identities().await
+        } else {
+            Ok(stream::iter(vec![]))
+        }
+    }
+}
+
+/// Returns [identifiers] of the block device holding filesystem with the given `uuid`.
+///
+/// UUIDs are compared case-insensitively.
+///
+/// [identifiers]: ./struct.FsIdentity.html
+pub async fn fs_identity_by_uuid(uuid: &str) -> Result<Option<FsIdentity>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::fs_identity_by_uuid(uuid).await
+        } else {
+            let _ = uuid;
+
+            Ok(None)
+        }
+    }
+}
+
+/// Returns [identifiers] of the block device holding the `partition` filesystem,
+/// such as UUID and label.
+///
+/// ## Returns
+///
+/// Returns `None` if partition is not backed by a block device
+/// or device is missing in the `/dev/disk/by-*` directories.
+///
+/// [identifiers]: ./struct.FsIdentity.html
+pub async fn fs_identity(partition: &Partition) -> Result<Option<FsIdentity>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::mount_fs_identity(partition.mount_info().clone()).await
+        } else {
+            let _ = partition;
+
+            Ok(None)
+        }
+    }
+}
@@ -0,0 +1,392 @@
+use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
+use std::time::Duration;
+
+use heim_common::units::{
+    information, information_rate, ratio, time, Information, InformationRate, Ratio, Time,
+};
+
+#[cfg(target_os = "linux")]
+use super::IoCountersExt;
+use crate::IoCounters;
+
+// `/proc/diskstats` I/O and sector counters are `unsigned long`, wrapping at 32 bits
+// on 32-bit systems only, while time fields are `unsigned int` everywhere.
+const COUNTERS_WRAP_AT_U32: bool = cfg!(target_pointer_width = "32");
+
+// Sector-based values are wrapping before they are converted into bytes.
+fn delta(before: u64, after: u64, scale: u64, wraps_at_u32: bool) -> u64 {
+    let (before, after) = (before / scale, after / scale);
+    let delta = if after >= before {
+        after - before
+    } else if wraps_at_u32 && before <= u64::from(u32::MAX) {
+        // Counter wrapped
+        u64::from(u32::MAX) - before + after + 1
+    } else {
+        // Counter was reset, ex. device was removed and attached again with the same name
+        after
+    };
+
+    delta * scale
+}
+
+fn delta_bytes(before: Information, after: Information) -> u64 {
+    delta(
+        before.get::<information::byte>(),
+        after.get::<information::byte>(),
+        512,
+        COUNTERS_WRAP_AT_U32,
+    )
+}
+
+fn delta_ms(before: Time, after: Time) -> u64 {
+    delta(
+        before.get::<time::millisecond>() as u64,
+        after.get::<time::millisecond>() as u64,
+        1,
+        true,
+    )
+}
+
+fn delta_opt<T, F>(before: Option<T>, after: Option<T>, f: F) -> Option<u64>
+where
+    F: FnOnce(T, T) -> u64,
+{
+    match (before, after) {
+        (Some(before), Some(after)) => Some(f(before, after)),
+        _ => None,
+    }
+}
+
+/// Extended disk statistics calculated from two [IoCounters] samples,
+/// same to the ones reported by the `iostat -x` command.
+///
+/// ```rust,no_run
+/// # use std::time::Duration;
+/// # use heim_common::prelude::*;
+/// # use heim_disk::os::linux::iostat;
+/// #
+/// # #[heim_derive::main]
+/// # async fn main() -> Result<()> {
+/// let before = heim_disk::io_counters().await?.try_collect::<Vec<_>>().await?;
+/// smol::Timer::after(Duration::from_secs(1)).await;
+/// let after = heim_disk::io_counters().await?.try_collect::<Vec<_>>().await?;
+///
+/// for stat in iostat(before, after, Duration::from_secs(1)) {
+///     println!("{:?}: {:?} utilized", stat.device_name(), stat.utilization());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [IoCounters]: ../../struct.IoCounters.html
+#[derive(Debug, Clone)]
+pub struct IoStat {
+    device_name: OsString,
+    elapsed: f64,
+    reads: u64,
+    read_merged: u64,
+    read_bytes: u64,
+    read_ms: u64,
+    writes: u64,
+    write_merged: u64,
+    write_bytes: u64,
+    write_ms: u64,
+    busy_ms: u64,
+    weighted_ms: u64,
+    discards: Option<u64>,
+    discard_bytes: Option<u64>,
+    discard_ms: Option<u64>,
+    flushes: Option<u64>,
+    flush_ms: Option<u64>,
+}
+
+impl IoStat {
+    /// Calculates statistics for the same device from two samples taken `elapsed` time apart.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if samples belong to different devices or if `elapsed` is zero.
+    pub fn new(before: &IoCounters, after: &IoCounters, elapsed: Duration) -> Option<IoStat> {
+        if before.device_name() != after.device_name() || elapsed == Duration::default() {
+            return None;
+        }
+
+        cfg_if::cfg_if! {
+            if #[cfg(target_os = "linux")] {
+                let count = |before, after| delta(before, after, 1, COUNTERS_WRAP_AT_U32);
+
+                Some(IoStat {
+                    device_name: after.device_name().to_os_string(),
+                    elapsed: elapsed.as_secs_f64(),
+                    reads: count(before.read_count(), after.read_count()),
+                    read_merged: delta_opt(
+                        before.read_merged_count(),
+                        after.read_merged_count(),
+                        count,
+                    )?,
+                    read_bytes: delta_bytes(before.read_bytes(), after.read_bytes()),
+                    read_ms: delta_ms(before.read_time(), after.read_time()),
+                    writes: count(before.write_count(), after.write_count()),
+                    write_merged: delta_opt(
+                        before.write_merged_count(),
+                        after.write_merged_count(),
+                        count,
+                    )?,
+                    write_bytes: delta_bytes(before.write_bytes(), after.write_bytes()),
+                    write_ms: delta_ms(before.write_time(), after.write_time()),
+                    busy_ms: delta_opt(before.busy_time(), after.busy_time(), delta_ms)?,
+                    weighted_ms: delta_ms(before.weighted_io_time(), after.weighted_io_time()),
+                    discards: delta_opt(before.discard_count(), after.discard_count(), count),
+                    discard_bytes: delta_opt(
+                        before.discard_bytes(),
+                        after.discard_bytes(),
+                        delta_bytes,
+                    ),
+                    discard_ms: delta_opt(before.discard_time(), after.discard_time(), delta_ms),
+                    flushes: delta_opt(before.flush_count(), after.flush_count(), count),
+                    flush_ms: delta_opt(before.flush_time(), after.flush_time(), delta_ms),
+                })
+            } else {
+                unimplemented!("For documentation rendering")
+            }
+        }
+    }
+
+    /// Returns disk device name.
+    pub fn device_name(&self) -> &OsStr {
+        &self.device_name
+    }
+
+    /// Returns number of completed reads per second (`r/s`).
+    pub fn reads_per_second(&self) -> f64 {
+        self.per_second(self.reads)
+    }
+
+    /// Returns number of completed writes per second (`w/s`).
+    pub fn writes_per_second(&self) -> f64 {
+        self.per_second(self.writes)
+    }
+
+    /// Returns number of merged reads per second (`rrqm/s`).
+    pub fn read_merges_per_second(&self) -> f64 {
+        self.per_second(self.read_merged)
+    }
+
+    /// Returns number of merged writes per second (`wrqm/s`).
+    pub fn write_merges_per_second(&self) -> f64 {
+        self.per_second(self.write_merged)
+    }
+
+    /// Returns read throughput (`rkB/s`).
+    pub fn read_rate(&self) -> InformationRate {
+        InformationRate::new::<information_rate::byte_per_second>(self.per_second(self.read_bytes))
+    }
+
+    /// Returns write throughput (`wkB/s`).
+    pub fn write_rate(&self) -> InformationRate {
+        InformationRate::new::<information_rate::byte_per_second>(self.per_second(self.write_bytes))
+    }
+
+    /// Returns average time for read requests to be served,
+    /// including the time spent in the queue (`r_await`).
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if there were no reads.
+    pub fn read_await(&self) -> Option<Time> {
+        Self::average(self.read_ms, self.reads)
+    }
+
+    /// Returns average time for write requests to be served,
+    /// including the time spent in the queue (`w_await`).
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if there were no writes.
+    pub fn write_await(&self) -> Option<Time> {
+        Self::average(self.write_ms, self.writes)
+    }
+
+    /// Returns average size of read requests (`rareq-sz`).
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if there were no reads.
+    pub fn average_read_size(&self) -> Option<Information> {
+        self.read_bytes
+            .checked_div(self.reads)
+            .map(Information::new::<information::byte>)
+    }
+
+    /// Returns average size of write requests (`wareq-sz`).
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if there were no writes.
+    pub fn average_write_size(&self) -> Option<Information> {
+        self.write_bytes
+            .checked_div(self.writes)
+            .map(Information::new::<information::byte>)
+    }
+
+    /// Returns average queue length of the requests issued to the device (`aqu-sz`).
+    pub fn average_queue_size(&self) -> f64 {
+        self.weighted_ms as f64 / (self.elapsed * 1_000.0)
+    }
+
+    /// Returns share of time during which device was busy doing I/O (`%util`).
+    ///
+    /// For devices serving requests in parallel, such as RAID arrays and SSDs,
+    /// this value does not reflect their performance limits.
+    pub fn utilization(&self) -> Ratio {
+        let util = self.busy_ms as f64 / (self.elapsed * 1_000.0);
+
+        Ratio::new::<ratio::ratio>(util.min(1.0) as f32)
+    }
+
+    /// Returns number of completed discard requests per second (`d/s`).
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.18, older versions always return `None`.
+    pub fn discards_per_second(&self) -> Option<f64> {
+        self.discards.map(|discards| self.per_second(discards))
+    }
+
+    /// Returns discard throughput (`dkB/s`).
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.18, older versions always return `None`.
+    pub fn discard_rate(&self) -> Option<InformationRate> {
+        self.discard_bytes.map(|bytes| {
+            InformationRate::new::<information_rate::byte_per_second>(self.per_second(bytes))
+        })
+    }
+
+    /// Returns average time for discard requests to be served (`d_await`).
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.18, older versions always return `None`.
+    /// Also returns `None` if there were no discards.
+    pub fn discard_await(&self) -> Option<Time> {
+        Self::average(self.discard_ms?, self.discards?)
+    }
+
+    /// Returns number of completed flush requests per second (`f/s`).
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 5.5, older versions always return `None`.
+    pub fn flushes_per_second(&self) -> Option<f64> {
+        self.flushes.map(|flushes| self.per_second(flushes))
+    }
+
+    /// Returns average time for flush requests to be served (`f_await`).
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 5.5, older versions always return `None`.
+    /// Also returns `None` if there were no flushes.
+    pub fn flush_await(&self) -> Option<Time> {
+        Self::average(self.flush_ms?, self.flushes?)
+    }
+
+    fn per_second(&self, value: u64) -> f64 {
+        value as f64 / self.elapsed
+    }
+
+    fn average(ms: u64, count: u64) -> Option<Time> {
+        if count > 0 {
+            Some(Time::new::<time::millisecond>(ms as f64 / count as f64))
+        } else {
+            None
+        }
+    }
+}
+
+/// Calculates [extended statistics] for all devices present in both `before` and `after` samples.
+///
+/// Devices which appeared or disappeared between samples are skipped.
+/// Result is ordered in the same way as the `after` sample.
+///
+/// [extended statistics]: ./struct.IoStat.html
+pub fn iostat<B, A>(before: B, after: A, elapsed: Duration) -> Vec<IoStat>
+where
+    B: IntoIterator<Item = IoCounters>,
+    A: IntoIterator<Item = IoCounters>,
+{
+    let before = before
+        .into_iter()
+        .map(|counters| (counters.device_name().to_os_string(), counters))
+        .collect::<HashMap<_, _>>();
+
+    after
+        .into_iter()
+        .filter_map(|after| {
+            let before = before.get(after.device_name())?;
+
+            IoStat::new(before, &after, elapsed)
+        })
+        .collect()
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use heim_common::units::{information_rate, ratio, time};
+
+    use super::{delta, iostat};
+    use crate::{sys, IoCounters};
+
+    fn counters(line: &str) -> IoCounters {
+        sys::IoCounters::from_str(line).unwrap().into()
+    }
+
+    #[test]
+    fn test_delta() {
+        assert_eq!(delta(10, 15, 1, false), 5);
+        // 32-bit counter wrapped
+        assert_eq!(delta(u64::from(u32::MAX) - 1, 3, 1, true), 5);
+        // 64-bit counter can only be reset
+        assert_eq!(delta(u64::from(u32::MAX) - 1, 3, 1, false), 3);
+        assert_eq!(delta(u64::from(u32::MAX) + 10, 3, 1, true), 3);
+    }
+
+    #[test]
+    fn test_iostat() {
+        let before = vec![
+            counters("8 0 sda 100 10 2000 50 200 20 4000 100 0 500 1000 0 0 0 0 0 0"),
+            counters("8 16 sdb 1 0 8 1 1 0 8 1 0 1 2"),
+        ];
+        let after = vec![
+            counters("8 0 sda 200 20 4048 150 400 40 8000 500 1 1500 3000 10 0 80 20 5 5"),
+            counters("8 32 sdc 1 0 8 1 1 0 8 1 0 1 2"),
+        ];
+
+        let stats = iostat(before, after, Duration::from_secs(2));
+        assert_eq!(stats.len(), 1);
+
+        let stat = &stats[0];
+        assert_eq!(stat.reads_per_second(), 50.0);
+        assert_eq!(stat.writes_per_second(), 100.0);
+        assert_eq!(
+            stat.read_rate().get::<information_rate::byte_per_second>(),
+            2048.0 * 512.0 / 2.0
+        );
+        assert_eq!(stat.read_await().unwrap().get::<time::millisecond>(), 1.0);
+        assert_eq!(stat.write_await().unwrap().get::<time::millisecond>(), 2.0);
+        assert_eq!(stat.average_queue_size(), 1.0);
+        assert_eq!(stat.utilization().get::<ratio::ratio>(), 0.5);
+        assert_eq!(stat.discards_per_second(), Some(5.0));
+        assert_eq!(
+            stat.discard_await().unwrap().get::<time::millisecond>(),
+            2.0
+        );
+        assert_eq!(stat.flush_await().unwrap().get::<time::millisecond>(), 1.0);
+    }
+}
@@ -0,0 +1,256 @@
+use heim_common::prelude::*;
+use heim_common::units::{information, ratio, Information, InformationRate, Ratio, Time};
+
+#[cfg(target_os = "linux")]
+use crate::sys;
+
+/// Synchronization action performed by the md array.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum MdSyncAction {
+    /// Initial synchronization or synchronization after the unclean shutdown.
+    Resync,
+    /// Rebuilding data onto the replacement or spare device.
+    Recovery,
+    /// Changing array geometry, ex. number of devices or RAID level.
+    Reshape,
+    /// Checking array consistency without fixing it.
+    Check,
+    /// Checking and fixing array inconsistencies.
+    Repair,
+}
+
+/// Synchronization progress of the md array.
+#[derive(Debug, Clone)]
+pub struct MdSync {
+    pub(crate) action: MdSyncAction,
+    pub(crate) completed: Option<Information>,
+    pub(crate) total: Option<Information>,
+    pub(crate) speed: Option<InformationRate>,
+    pub(crate) remaining: Option<Time>,
+    pub(crate) delayed: bool,
+}
+
+impl MdSync {
+    /// Returns synchronization action.
+    pub fn action(&self) -> MdSyncAction {
+        self.action
+    }
+
+    /// Returns amount of already synchronized data.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if synchronization is [delayed].
+    ///
+    /// [delayed]: #method.is_delayed
+    pub fn completed(&self) -> Option<Information> {
+        self.completed
+    }
+
+    /// Returns total amount of data to synchronize.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if synchronization is [delayed].
+    ///
+    /// [delayed]: #method.is_delayed
+    pub fn total(&self) -> Option<Information> {
+        self.total
+    }
+
+    /// Returns synchronization progress.
+    pub fn progress(&self) -> Option<Ratio> {
+        let completed = self.completed?.get::<information::byte>();
+        let total = self.total?.get::<information::byte>();
+        if total == 0 {
+            return None;
+        }
+
+        Some(Ratio::new::<ratio::ratio>(
+            (completed as f64 / total as f64) as f32,
+        ))
+    }
+
+    /// Returns current synchronization speed.
+    pub fn speed(&self) -> Option<InformationRate> {
+        self.speed
+    }
+
+    /// Returns estimated time until the synchronization is finished.
+    pub fn remaining(&self) -> Option<Time> {
+        self.remaining
+    }
+
+    /// Returns `true` if synchronization is waiting for another array
+    /// sharing the same devices to finish its synchronization.
+    pub fn is_delayed(&self) -> bool {
+        self.delayed
+    }
+}
+
+/// Member device of the md array.
+#[derive(Debug, Clone)]
+pub struct MdMember {
+    pub(crate) name: String,
+    pub(crate) descriptor: u32,
+    pub(crate) slot: Option<u32>,
+    pub(crate) faulty: bool,
+    pub(crate) spare: bool,
+    pub(crate) write_mostly: bool,
+    pub(crate) replacement: bool,
+    pub(crate) state: Vec<String>,
+}
+
+impl MdMember {
+    /// Returns member block device name, ex. `sda1`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns member descriptor number in the array superblock.
+    pub fn descriptor(&self) -> u32 {
+        self.descriptor
+    }
+
+    /// Returns member role in the array, ex. `0` for the first mirror in the RAID1.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` for spare and faulty devices which do not have an active role,
+    /// or if `/sys/block/md*/md/` directory is not available.
+    pub fn slot(&self) -> Option<u32> {
+        self.slot
+    }
+
+    /// Returns `true` if device has failed and is not used anymore.
+    pub fn is_faulty(&self) -> bool {
+        self.faulty
+    }
+
+    /// Returns `true` if device is a spare one.
+    pub fn is_spare(&self) -> bool {
+        self.spare
+    }
+
+    /// Returns `true` if reads are avoided from this device when possible.
+    pub fn is_write_mostly(&self) -> bool {
+        self.write_mostly
+    }
+
+    /// Returns `true` if device is a replacement for another device with the same role.
+    pub fn is_replacement(&self) -> bool {
+        self.replacement
+    }
+
+    /// Returns raw device state flags from the `/sys/block/md*/md/dev-*/state` file,
+    /// ex. `in_sync` or `write_error`.
+    pub fn state(&self) -> &[String] {
+        &self.state
+    }
+}
+
+/// Linux software RAID (md) array.
+///
+/// Values are read from the `/proc/mdstat` file and `/sys/block/md*/md/` directories,
+/// see `Documentation/admin-guide/md.rst` in the kernel source for details.
+#[derive(Debug, Clone)]
+pub struct MdArray {
+    pub(crate) name: String,
+    pub(crate) active: bool,
+    pub(crate) read_only: bool,
+    pub(crate) level: Option<String>,
+    pub(crate) array_state: Option<String>,
+    pub(crate) size: Option<Information>,
+    pub(crate) raid_disks: Option<u32>,
+    pub(crate) active_disks: Option<u32>,
+    pub(crate) degraded: Option<u32>,
+    pub(crate) members: Vec<MdMember>,
+    pub(crate) sync: Option<MdSync>,
+}
+
+impl MdArray {
+    /// Returns array device name, ex. `md0`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns `true` if array is active.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Returns `true` if array is read-only or auto-read-only.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Returns RAID level, ex. `raid1`, `raid10` or `linear`.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` for inactive arrays.
+    pub fn level(&self) -> Option<&str> {
+        self.level.as_deref()
+    }
+
+    /// Returns array state from the `/sys/block/md*/md/array_state` file,
+    /// ex. `clean`, `active` or `read-auto`.
+    pub fn array_state(&self) -> Option<&str> {
+        self.array_state.as_deref()
+    }
+
+    /// Returns usable array size.
+    pub fn size(&self) -> Option<Information> {
+        self.size
+    }
+
+    /// Returns number of devices array should consist of.
+    pub fn raid_disks(&self) -> Option<u32> {
+        self.raid_disks
+    }
+
+    /// Returns number of devices which are currently working in array.
+    pub fn active_disks(&self) -> Option<u32> {
+        self.active_disks
+    }
+
+    /// Returns number of missing devices.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` for RAID levels without redundancy, such as `raid0` and `linear`.
+    pub fn degraded(&self) -> Option<u32> {
+        self.degraded
+    }
+
+    /// Returns `true` if array lacks some devices and works without full redundancy.
+    pub fn is_degraded(&self) -> bool {
+        self.degraded.map(|count| count > 0).unwrap_or(false)
+    }
+
+    /// Returns array member devices.
+    pub fn members(&self) -> &[MdMember] {
+        &self.members
+    }
+
+    /// Returns synchronization progress if there is one running or pending.
+    pub fn sync(&self) -> Option<&MdSync> {
+        self.sync.as_ref()
+    }
+}
+
+/// Returns a stream over Linux software RAID [arrays].
+///
+/// Stream is empty if `md` driver is not loaded.
+///
+/// [arrays]: ./struct.MdArray.html
+pub async fn md_arrays() -> Result<impl Stream<Item = Result<MdArray>>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::md_arrays().await
+        } else {
+            Ok(stream::iter(vec![]))
+        }
+    }
+}
@@ -0,0 +1,23 @@
+//! Linux-specific extensions.
+
+mod backing;
+mod block;
+mod btrfs;
+mod counters;
+mod dm;
+mod identity;
+mod iostat;
+mod md;
+mod mountinfo;
+mod partitions;
+
+pub use self::backing::*;
+pub use self::block::*;
+pub use self::btrfs::*;
+pub use self::counters::*;
+pub use self::dm::*;
+pub use self::identity::*;
+pub use self::iostat::*;
+pub use self::md::*;
+pub use self::mountinfo::*;
+pub use self::partitions::*;
@@ -0,0 +1,211 @@
+use std::path::{Path, PathBuf};
+
+use heim_common::prelude::*;
+
+#[cfg(target_os = "linux")]
+use crate::sys;
+use crate::FileSystem;
+
+/// Mount entry from the `/proc/self/mountinfo` file.
+///
+/// See `proc(5)` for fields description.
+#[derive(Debug, Clone)]
+pub struct MountInfo {
+    pub(crate) mount_id: u32,
+    pub(crate) parent_id: u32,
+    pub(crate) major: u32,
+    pub(crate) minor: u32,
+    pub(crate) root: PathBuf,
+    pub(crate) mount_point: PathBuf,
+    pub(crate) mount_options: String,
+    pub(crate) optional_fields: Vec<String>,
+    pub(crate) file_system: FileSystem,
+    pub(crate) source: Option<String>,
+    pub(crate) super_options: String,
+}
+
+impl MountInfo {
+    /// Returns unique mount ID.
+    ///
+    /// IDs may be reused after unmount.
+    pub fn mount_id(&self) -> u32 {
+        self.mount_id
+    }
+
+    /// Returns ID of the parent mount.
+    ///
+    /// For the top of the mount tree it is the mount's own ID
+    /// or an ID of the mount outside of the process mount namespace.
+    pub fn parent_id(&self) -> u32 {
+        self.parent_id
+    }
+
+    /// Returns major number of the device backing this mount.
+    pub fn major(&self) -> u32 {
+        self.major
+    }
+
+    /// Returns minor number of the device backing this mount.
+    pub fn minor(&self) -> u32 {
+        self.minor
+    }
+
+    /// Returns path of the directory in the filesystem which forms the root of this mount,
+    /// it differs from `/` for bind mounts.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns mount point path.
+    pub fn mount_point(&self) -> &Path {
+        &self.mount_point
+    }
+
+    /// Returns per-mount options, ex. `rw,nosuid,relatime`.
+    pub fn mount_options(&self) -> &str {
+        &self.mount_options
+    }
+
+    /// Returns optional fields, ex. `shared:1` or `master:2`.
+    pub fn optional_fields(&self) -> &[String] {
+        &self.optional_fields
+    }
+
+    /// Returns peer group ID if this mount is shared.
+    pub fn shared(&self) -> Option<u32> {
+        self.tagged_field("shared:")
+    }
+
+    /// Returns peer group ID of the master if this mount is a slave.
+    pub fn master(&self) -> Option<u32> {
+        self.tagged_field("master:")
+    }
+
+    /// Returns ID of the closest dominant peer group this mount receives propagation from.
+    pub fn propagate_from(&self) -> Option<u32> {
+        self.tagged_field("propagate_from:")
+    }
+
+    /// Returns `true` if this mount is unbindable.
+    pub fn is_unbindable(&self) -> bool {
+        self.optional_fields
+            .iter()
+            .any(|field| field == "unbindable")
+    }
+
+    /// Returns mounted file system.
+    pub fn file_system(&self) -> &FileSystem {
+        &self.file_system
+    }
+
+    /// Returns filesystem-specific mount source, ex. `/dev/sda1`.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if source is `none` or empty.
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    /// Returns per-superblock options, shared by all mounts of the same filesystem.
+    pub fn super_options(&self) -> &str {
+        &self.super_options
+    }
+
+    fn tagged_field(&self, tag: &str) -> Option<u32> {
+        self.optional_fields
+            .iter()
+            .find_map(|field| field.strip_prefix(tag))
+            .and_then(|value| value.parse().ok())
+    }
+}
+
+/// Mount tree of the current process mount namespace.
+#[derive(Debug, Clone)]
+pub struct MountTable {
+    pub(crate) mounts: Vec<MountInfo>,
+}
+
+impl MountTable {
+    /// Returns all mounts in the order they were mounted.
+    pub fn mounts(&self) -> &[MountInfo] {
+        &self.mounts
+    }
+
+    /// Returns mount with the given `mount_id`.
+    pub fn get(&self, mount_id: u32) -> Option<&MountInfo> {
+        self.mounts.iter().find(|mount| mount.mount_id == mount_id)
+    }
+
+    /// Returns mount which `mount` is attached to.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` for the root of the mount tree.
+    pub fn parent(&self, mount: &MountInfo) -> Option<&MountInfo> {
+        if mount.parent_id == mount.mount_id {
+            return None;
+        }
+
+        self.get(mount.parent_id)
+    }
+
+    /// Returns mounts attached directly to the `mount`.
+    pub fn children<'a>(&'a self, mount: &'a MountInfo) -> impl Iterator<Item = &'a MountInfo> {
+        self.mounts.iter().filter(move |child| {
+            child.parent_id == mount.mount_id && child.mount_id != mount.mount_id
+        })
+    }
+
+    /// Returns the mount which `mount` was bind-mounted from.
+    ///
+    /// It is the earliest mount of the same filesystem which root contains the `mount` root.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if `mount` is not a bind mount
+    /// or if the original mount is not visible in this mount namespace.
+    pub fn bind_source(&self, mount: &MountInfo) -> Option<&MountInfo> {
+        self.mounts
+            .iter()
+            .filter(|other| {
+                other.mount_id != mount.mount_id
+                    && (other.major, other.minor) == (mount.major, mount.minor)
+                    && mount.root.starts_with(&other.root)
+            })
+            // Mount of the same root earlier in the table is the original one
+            .find(|other| other.root != mount.root || self.position(other) < self.position(mount))
+    }
+
+    /// Returns path of the `mount` root directory, as visible through its [bind source].
+    ///
+    /// [bind source]: #method.bind_source
+    pub fn bind_source_path(&self, mount: &MountInfo) -> Option<PathBuf> {
+        let source = self.bind_source(mount)?;
+        let relative = mount.root.strip_prefix(&source.root).ok()?;
+
+        Some(source.mount_point.join(relative))
+    }
+
+    fn position(&self, mount: &MountInfo) -> Option<usize> {
+        self.mounts
+            .iter()
+            .position(|other| other.mount_id == mount.mount_id)
+    }
+}
+
+/// Returns [mount tree] of the current process from the `/proc/self/mountinfo` file.
+///
+/// Mount points and sources containing spaces and other special characters
+/// are decoded from the kernel octal escapes.
+///
+/// [mount tree]: ./struct.MountTable.html
+pub async fn mount_table() -> Result<MountTable> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::mount_table().await
+        } else {
+            unimplemented!("For documentation rendering")
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use super::MountInfo;
+
+/// Linux-specific extension for [Partition] struct.
+///
+/// [Partition]: ../../struct.Partition.html
+pub trait PartitionExt {
+    /// Returns `/proc/self/mountinfo` entry for this partition.
+    fn mount_info(&self) -> &MountInfo;
+}
+
+#[cfg(target_os = "linux")]
+impl PartitionExt for crate::Partition {
+    fn mount_info(&self) -> &MountInfo {
+        self.as_ref().mount_info()
+    }
+}
@@ -6,6 +6,10 @@
 #[cfg(unix)]
 pub mod unix;
 
+#[cfg(any(target_os = "linux", doc))]
+#[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
+pub mod linux;
+
 // TODO: These too
 //#[cfg(any(target_os = "macos", doc))]
 //#[cfg_attr(docsrs, doc(cfg(target_os = "macos")))]
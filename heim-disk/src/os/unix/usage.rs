@@ -1,3 +1,5 @@
+use heim_common::units::{Information, Ratio};
+
 bitflags::bitflags! {
     /// Various options that were employed when mounting this filesystem (see `statvfs(3)`).
     ///
@@ -75,6 +77,38 @@ bitflags::bitflags! {
 pub trait UsageExt {
     /// Returns [Flags] for current filesystem;
     fn flags(&self) -> Flags;
+
+    /// Returns total number of inodes (file nodes) in the filesystem.
+    fn inodes_total(&self) -> u64;
+
+    /// Returns number of free inodes, including the ones reserved for the superuser.
+    fn inodes_free(&self) -> u64;
+
+    /// Returns number of inodes available to unprivileged users.
+    fn inodes_available(&self) -> u64;
+
+    /// Returns the ratio between used and available to unprivileged users inodes,
+    /// same as the `IUse%` column of the `df -i` command.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if filesystem does not report inodes count (ex. btrfs or FAT).
+    fn inodes_ratio(&self) -> Option<Ratio>;
+
+    /// Returns preferred I/O block size of the filesystem.
+    fn block_size(&self) -> Information;
+
+    /// Returns fundamental block size of the filesystem,
+    /// all the [Usage] information amounts are multiples of it.
+    ///
+    /// [Usage]: ../../struct.Usage.html
+    fn fragment_size(&self) -> Information;
+
+    /// Returns filesystem ID.
+    fn filesystem_id(&self) -> u64;
+
+    /// Returns maximum file name length in the filesystem.
+    fn max_filename_length(&self) -> u64;
 }
 
 #[cfg(unix)]
@@ -82,4 +116,36 @@ impl UsageExt for crate::Usage {
     fn flags(&self) -> Flags {
         self.as_ref().flags()
     }
+
+    fn inodes_total(&self) -> u64 {
+        self.as_ref().inodes_total()
+    }
+
+    fn inodes_free(&self) -> u64 {
+        self.as_ref().inodes_free()
+    }
+
+    fn inodes_available(&self) -> u64 {
+        self.as_ref().inodes_available()
+    }
+
+    fn inodes_ratio(&self) -> Option<Ratio> {
+        self.as_ref().inodes_ratio()
+    }
+
+    fn block_size(&self) -> Information {
+        self.as_ref().block_size()
+    }
+
+    fn fragment_size(&self) -> Information {
+        self.as_ref().fragment_size()
+    }
+
+    fn filesystem_id(&self) -> u64 {
+        self.as_ref().filesystem_id()
+    }
+
+    fn max_filename_length(&self) -> u64 {
+        self.as_ref().max_filename_length()
+    }
 }
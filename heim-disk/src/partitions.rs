@@ -1,8 +1,12 @@
 use std::ffi::OsStr;
 use std::fmt;
-use std::path::Path;
+use std::io;
+use std::path::{Path, PathBuf};
+#[cfg(not(target_os = "linux"))]
+use std::time::Duration;
 
 use heim_common::prelude::*;
+use heim_runtime as rt;
 
 use crate::{sys, usage, FileSystem, Usage};
 
@@ -13,6 +17,7 @@ use crate::{sys, usage, FileSystem, Usage};
 /// See [os]-specific extension traits also.
 ///
 /// [os]: ./os/index.html
+#[derive(Clone)]
 pub struct Partition(sys::Partition);
 
 wrap!(Partition, sys::Partition);
@@ -60,6 +65,10 @@ impl fmt::Debug for Partition {
 /// This includes all virtual partitions, such as `tmpfs`.
 /// See [partitions_physical] for physical partitions stream.
 ///
+/// On Linux, partitions are read from the `/proc/self/mountinfo` file,
+/// see [`os::linux::PartitionExt`] for the additional mount details.
+///
+/// [`os::linux::PartitionExt`]: ./os/linux/trait.PartitionExt.html
 /// [Partitions]: struct.Partition.html
 pub async fn partitions() -> Result<impl Stream<Item = Result<Partition>>> {
     let inner = sys::partitions().await?;
@@ -69,9 +78,270 @@ pub async fn partitions() -> Result<impl Stream<Item = Result<Partition>>> {
 
 /// Returns a stream over physical only mounted disk [Partitions].
 ///
+/// Partitions are selected with the [`FileSystem::is_physical`] classification.
+/// On Linux, filesystems not known to the [`FileSystem`] are checked against
+/// the `/proc/filesystems` list additionally and are treated as physical ones
+/// if they require a block device there.
+///
 /// [Partitions]: struct.Partition.html
+/// [`FileSystem`]: enum.FileSystem.html
+/// [`FileSystem::is_physical`]: enum.FileSystem.html#method.is_physical
 pub async fn partitions_physical() -> Result<impl Stream<Item = Result<Partition>>> {
     let inner = sys::partitions_physical().await?;
 
     Ok(inner.map_ok(Into::into))
 }
+
+/// Returns a stream over mounted disk [Partitions] with file systems matching the `filter`.
+///
+/// [`FileSystem`] classification methods can be used as a filter directly:
+///
+/// ```rust,no_run
+/// # use heim_common::prelude::*;
+/// # use heim_disk::FileSystem;
+/// #
+/// # #[heim_derive::main]
+/// # async fn main() -> Result<()> {
+/// let network = heim_disk::partitions_filtered(FileSystem::is_network).await?;
+///
+/// let local = heim_disk::partitions_filtered(|fs: &FileSystem| {
+///     fs.is_physical() && !fs.is_read_only_image()
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [Partitions]: struct.Partition.html
+/// [`FileSystem`]: enum.FileSystem.html
+pub async fn partitions_filtered<F>(filter: F) -> Result<impl Stream<Item = Result<Partition>>>
+where
+    F: Fn(&FileSystem) -> bool,
+{
+    let inner = partitions().await?;
+
+    Ok(inner.try_filter(move |partition| future::ready(filter(partition.file_system()))))
+}
+
+/// Returns mounted [Partition] which contains the given `path`.
+///
+/// Symbolic links in the `path` are resolved first and then the partition
+/// with the longest mount point matching the path is selected;
+/// if several partitions are mounted on the same mount point, the last mounted one wins.
+/// Bind and overlay mounts are returned as is, see [`os::linux::backing_device`]
+/// to find the block device behind them.
+///
+/// ## Errors
+///
+/// Returns an error if the `path` does not exist or if no partition contains it.
+///
+/// [Partition]: ./struct.Partition.html
+/// [`os::linux::backing_device`]: ./os/linux/fn.backing_device.html
+pub async fn mount_for_path<T>(path: T) -> Result<Partition>
+where
+    T: AsRef<Path>,
+{
+    let path = path.as_ref().to_path_buf();
+    let path = resolve_path(path.clone())
+        .await
+        .map_err(|e| Error::from(e).with_file(path))?;
+
+    let partitions = partitions().await?.try_collect::<Vec<_>>().await?;
+
+    find_mount(&partitions, &path, Partition::mount_point)
+        .cloned()
+        .ok_or_else(|| {
+            let inner = io::Error::from(io::ErrorKind::NotFound);
+            Error::from(inner).with_file(path)
+        })
+}
+
+// Selects the mount with the longest mount point containing the `path`,
+// later mounts are shadowing the earlier ones on the same mount point.
+pub(crate) fn find_mount<'a, T, F>(mounts: &'a [T], path: &Path, mount_point: F) -> Option<&'a T>
+where
+    F: Fn(&T) -> &Path,
+{
+    mounts
+        .iter()
+        .filter(|mount| path.starts_with(mount_point(mount)))
+        .max_by_key(|mount| mount_point(mount).components().count())
+}
+
+/// Mount table change, emitted by the [watch_partitions] stream.
+///
+/// [watch_partitions]: ./fn.watch_partitions.html
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)] // Boxing would make matching on events harder
+pub enum PartitionEvent {
+    /// Partition was mounted.
+    Mounted(Partition),
+    /// Partition was unmounted.
+    Unmounted(Partition),
+    /// Partition was remounted with different options, ex. switched to read-only mode.
+    Remounted {
+        /// Partition before the remount.
+        old: Partition,
+        /// Partition after the remount.
+        new: Partition,
+    },
+}
+
+/// Polling interval for systems without mount change notifications.
+#[cfg(not(target_os = "linux"))]
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Returns a stream over [mount table changes].
+///
+/// Events are calculated by comparing partitions lists before and after each change,
+/// so partition mounted and unmounted in between is not reported at all.
+/// Mount table is re-read after each change, and the stream does not end
+/// if re-reading fails.
+///
+/// ## Compatibility
+///
+/// On Linux, kernel notifies about the changes via `POLLPRI` event
+/// for the `/proc/self/mountinfo` file.
+/// For other platforms mounted partitions are polled once per second.
+///
+/// [mount table changes]: ./enum.PartitionEvent.html
+pub async fn watch_partitions() -> Result<impl Stream<Item = Result<PartitionEvent>>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            // Watching starts before the first read, so no changes are missed
+            let changes = sys::mount_changes().await?.boxed();
+        } else {
+            let changes = rt::time::interval(WATCH_INTERVAL).map(Ok).boxed();
+        }
+    }
+    let current = partitions().await?.try_collect::<Vec<_>>().await?;
+
+    let stream = stream::unfold(Some((changes, current)), |state| async move {
+        let (mut changes, previous) = state?;
+        match changes.next().await? {
+            Ok(()) => {}
+            Err(e) => return Some((vec![Err(e)], None)),
+        }
+
+        let current = match partitions().await {
+            Ok(stream) => stream.try_collect::<Vec<_>>().await,
+            Err(e) => Err(e),
+        };
+        match current {
+            Ok(current) => {
+                let events = diff(&previous, &current);
+                Some((
+                    events.into_iter().map(Ok).collect(),
+                    Some((changes, current)),
+                ))
+            }
+            Err(e) => Some((vec![Err(e)], Some((changes, previous)))),
+        }
+    });
+
+    Ok(stream.flat_map(stream::iter))
+}
+
+fn diff(previous: &[Partition], current: &[Partition]) -> Vec<PartitionEvent> {
+    let mut events = Vec::new();
+
+    for old in previous {
+        let is_mounted = current
+            .iter()
+            .any(|new| new.as_ref().is_same_mount(old.as_ref()));
+        if !is_mounted {
+            events.push(PartitionEvent::Unmounted(old.clone()));
+        }
+    }
+
+    for new in current {
+        match previous
+            .iter()
+            .find(|old| old.as_ref().is_same_mount(new.as_ref()))
+        {
+            Some(old) if old.as_ref().is_remounted(new.as_ref()) => {
+                events.push(PartitionEvent::Remounted {
+                    old: old.clone(),
+                    new: new.clone(),
+                });
+            }
+            Some(..) => {}
+            None => events.push(PartitionEvent::Mounted(new.clone())),
+        }
+    }
+
+    events
+}
+
+#[cfg(unix)]
+async fn resolve_path(path: PathBuf) -> io::Result<PathBuf> {
+    rt::spawn_blocking(move || path.canonicalize()).await
+}
+
+// `canonicalize` returns `\\?\C:\` verbatim paths, which are not matching
+// the drive mount points, so path is only checked for existence
+#[cfg(not(unix))]
+async fn resolve_path(path: PathBuf) -> io::Result<PathBuf> {
+    rt::spawn_blocking(move || path.metadata().map(|_| path)).await
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use std::path::Path;
+
+    use super::{diff, Partition, PartitionEvent};
+    use crate::sys;
+
+    fn partitions(mountinfo: &str) -> Vec<Partition> {
+        sys::parse_mountinfo(mountinfo)
+            .into_iter()
+            .map(|info| Partition::from(sys::Partition::from(info)))
+            .collect()
+    }
+
+    #[test]
+    fn test_diff() {
+        let previous = partitions(
+            "22 1 8:1 / / rw,relatime - ext4 /dev/sda1 rw
+40 22 8:17 / /mnt rw,relatime - ext4 /dev/sdb1 rw
+41 22 8:33 / /data rw,relatime - xfs /dev/sdc1 rw
+",
+        );
+        let current = partitions(
+            "22 1 8:1 / / ro,relatime - ext4 /dev/sda1 ro
+40 22 8:49 / /media/usb rw,relatime - vfat /dev/sdd1 rw
+41 22 8:33 / /data rw,relatime - xfs /dev/sdc1 rw
+50 22 0:45 / /tmp rw,nosuid - tmpfs tmpfs rw
+",
+        );
+
+        let events = diff(&previous, &current);
+        assert_eq!(events.len(), 4);
+
+        // Mount ID 40 was reused by the new mount
+        match &events[0] {
+            PartitionEvent::Unmounted(old) => assert_eq!(old.mount_point(), Path::new("/mnt")),
+            other => panic!("Unexpected event: {:?}", other),
+        }
+        match &events[1] {
+            PartitionEvent::Remounted { old, new } => {
+                assert_eq!(old.mount_point(), Path::new("/"));
+                assert_eq!(old.as_ref().options(), "rw,relatime");
+                assert_eq!(new.as_ref().options(), "ro,relatime");
+            }
+            other => panic!("Unexpected event: {:?}", other),
+        }
+        match &events[2] {
+            PartitionEvent::Mounted(new) => {
+                assert_eq!(new.mount_point(), Path::new("/media/usb"))
+            }
+            other => panic!("Unexpected event: {:?}", other),
+        }
+        match &events[3] {
+            PartitionEvent::Mounted(new) => assert_eq!(new.mount_point(), Path::new("/tmp")),
+            other => panic!("Unexpected event: {:?}", other),
+        }
+
+        assert!(diff(&current, &current).is_empty());
+    }
+}
@@ -0,0 +1,102 @@
+use std::fs;
+
+use heim_common::prelude::*;
+use heim_common::sys::linux::unescape_octal;
+use heim_runtime as rt;
+
+use super::{disks_for, mount_device, read_dev, read_mountinfo};
+use crate::os::linux::{BackingDevice, MountInfo};
+use crate::{find_mount, FileSystem};
+
+// Overlays can be stacked, but not too deep
+const MAX_OVERLAY_DEPTH: usize = 8;
+
+// Example: `rw,lowerdir=/lower,upperdir=/upper,workdir=/work`
+fn upper_dir(super_options: &str) -> Option<String> {
+    super_options
+        .split(',')
+        .find_map(|option| option.strip_prefix("upperdir="))
+        .map(|dir| unescape_octal(dir).into_owned())
+}
+
+fn resolve(info: &MountInfo, depth: usize) -> Result<BackingDevice> {
+    if *info.file_system() == FileSystem::Overlay && depth < MAX_OVERLAY_DEPTH {
+        // Upper directory might be not visible, ex. inside of the container,
+        // overlay mount device numbers are used then
+        if let Some(Ok(upper)) = upper_dir(info.super_options()).map(fs::canonicalize) {
+            let mounts = read_mountinfo()?;
+            if let Some(mount) = find_mount(&mounts, &upper, MountInfo::mount_point) {
+                if mount.mount_id() != info.mount_id() {
+                    return resolve(mount, depth + 1);
+                }
+            }
+        }
+    }
+
+    let sysfs = rt::linux::sysfs_root();
+    let device = match mount_device(sysfs, info) {
+        Some(name) => {
+            let path = sysfs.join("class/block").join(name.replace('/', "!"));
+            let (major, minor) = read_dev(&path.join("dev"))?;
+
+            BackingDevice {
+                major,
+                minor,
+                disks: disks_for(sysfs, &name),
+                name: Some(name),
+            }
+        }
+        None => BackingDevice {
+            major: info.major(),
+            minor: info.minor(),
+            name: None,
+            disks: Vec::new(),
+        },
+    };
+
+    Ok(device)
+}
+
+pub async fn backing_device(info: MountInfo) -> Result<BackingDevice> {
+    rt::spawn_blocking(move || resolve(&info, 0)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::upper_dir;
+    use crate::find_mount;
+    use crate::os::linux::MountInfo;
+    use crate::sys::parse_mountinfo;
+
+    #[test]
+    fn test_find_mount() {
+        let mounts = parse_mountinfo(
+            "22 1 8:1 / / rw,relatime - ext4 /dev/sda1 rw
+30 22 8:17 / /var rw,relatime - ext4 /dev/sdb1 rw
+31 30 0:45 / /var/lib rw,relatime - tmpfs tmpfs rw
+32 30 0:46 / /var/lib rw,relatime - tmpfs tmpfs rw
+",
+        );
+
+        let mount = |path| {
+            find_mount(&mounts, Path::new(path), MountInfo::mount_point)
+                .unwrap()
+                .mount_id()
+        };
+        assert_eq!(mount("/home"), 22);
+        assert_eq!(mount("/var/log"), 30);
+        assert_eq!(mount("/var/lib/postgres"), 32);
+        assert_eq!(mount("/variable"), 22);
+    }
+
+    #[test]
+    fn test_upper_dir() {
+        assert_eq!(
+            upper_dir("rw,lowerdir=/lower,upperdir=/var/lib/upper\\040dir,workdir=/work"),
+            Some("/var/lib/upper dir".to_string())
+        );
+        assert_eq!(upper_dir("ro,lowerdir=/lower"), None);
+    }
+}
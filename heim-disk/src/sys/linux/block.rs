@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use heim_common::prelude::*;
+use heim_common::sys::linux::parse_selected;
+use heim_common::units::{information, Information};
+use heim_runtime as rt;
+
+use super::{read_identities, DISK_SECTOR_SIZE};
+use crate::os::linux::{BlockDevice, BlockPartition, FsIdentity};
+
+// Kernel replaces `/` in the device names with `!` in sysfs, ex. `cciss!c0d0`
+pub fn device_name(sysfs_name: &str) -> String {
+    sysfs_name.replace('!', "/")
+}
+
+pub fn read_string(path: &Path) -> Option<String> {
+    let contents = fs::read_to_string(path).ok()?;
+    let value = contents.trim();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+fn read_value<T: std::str::FromStr>(path: &Path) -> Option<T> {
+    read_string(path)?.parse().ok()
+}
+
+pub fn read_flag(path: &Path) -> Option<bool> {
+    read_value::<u8>(path).map(|value| value != 0)
+}
+
+pub fn read_bytes(path: &Path) -> Option<Information> {
+    read_value(path).map(Information::new::<information::byte>)
+}
+
+// `size` and `start` files are always expressed in 512-byte sectors,
+// regardless of the `queue/hw_sector_size` value
+fn read_sectors(path: &Path) -> Result<Information> {
+    let contents = fs::read_to_string(path).map_err(|e| Error::from(e).with_file(path))?;
+    let sectors = contents
+        .trim()
+        .parse::<u64>()
+        .map_err(|e| Error::from(e).with_file(path))?;
+
+    Ok(Information::new::<information::byte>(
+        sectors * DISK_SECTOR_SIZE,
+    ))
+}
+
+// Example: `259:0`
+fn parse_dev(contents: &str) -> Result<(u32, u32)> {
+    let mut parts = contents.trim().splitn(2, ':');
+    match (parts.next(), parts.next()) {
+        (Some(major), Some(minor)) => Ok((major.parse()?, minor.parse()?)),
+        _ => {
+            let inner = io::Error::from(io::ErrorKind::InvalidData);
+            Err(Error::from(inner).with_message(format!("Invalid device number: {}", contents)))
+        }
+    }
+}
+
+pub fn read_dev(path: &Path) -> Result<(u32, u32)> {
+    let contents = fs::read_to_string(path).map_err(|e| Error::from(e).with_file(path))?;
+
+    parse_dev(&contents).map_err(|e| e.with_file(path))
+}
+
+// Example: `none [mq-deadline] kyber bfq`, or just `none` for some devices
+fn parse_scheduler(contents: &str) -> Option<&str> {
+    parse_selected(contents).or_else(
+        || match contents.split_whitespace().collect::<Vec<_>>()[..] {
+            [scheduler] => Some(scheduler),
+            _ => None,
+        },
+    )
+}
+
+pub fn list_dir(path: &Path) -> Vec<String> {
+    let mut names = match fs::read_dir(path) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .map(|name| device_name(&name))
+            .collect::<Vec<_>>(),
+        Err(..) => Vec::new(),
+    };
+    names.sort();
+
+    names
+}
+
+// Returns whole disk name for the partition, or the same name for whole disks
+fn whole_disk(sysfs: &Path, name: &str) -> String {
+    let path = sysfs.join("class/block").join(name.replace('/', "!"));
+    if !path.join("partition").exists() {
+        return name.to_string();
+    }
+
+    // `/sys/class/block/sda1` is a symlink into the parent disk directory
+    fs::canonicalize(&path)
+        .ok()
+        .and_then(|path| {
+            path.parent()
+                .and_then(|parent| parent.file_name())
+                .and_then(|name| name.to_str())
+                .map(device_name)
+        })
+        .unwrap_or_else(|| name.to_string())
+}
+
+fn backing_disks(sysfs: &Path, slaves: &[String], acc: &mut Vec<String>, depth: usize) {
+    // Device stacks are shallow, limit is here just in case of the sysfs loops
+    if depth > 16 {
+        return;
+    }
+
+    for slave in slaves {
+        let disk = whole_disk(sysfs, slave);
+        let next = list_dir(
+            &sysfs
+                .join("block")
+                .join(disk.replace('/', "!"))
+                .join("slaves"),
+        );
+        if next.is_empty() {
+            if !acc.contains(&disk) {
+                acc.push(disk);
+            }
+        } else {
+            backing_disks(sysfs, &next, acc, depth + 1);
+        }
+    }
+}
+
+// Returns whole disks at the bottom of the `name` device stack, or the device itself
+pub fn disks_for(sysfs: &Path, name: &str) -> Vec<String> {
+    let disk = whole_disk(sysfs, name);
+    let slaves = list_dir(
+        &sysfs
+            .join("block")
+            .join(disk.replace('/', "!"))
+            .join("slaves"),
+    );
+    if slaves.is_empty() {
+        return vec![disk];
+    }
+
+    let mut disks = Vec::new();
+    backing_disks(sysfs, &slaves, &mut disks, 0);
+
+    disks
+}
+
+fn partition(
+    path: &Path,
+    name: &str,
+    identities: &HashMap<String, FsIdentity>,
+) -> Result<BlockPartition> {
+    let (major, minor) = read_dev(&path.join("dev"))?;
+    let name = device_name(name);
+
+    Ok(BlockPartition {
+        identity: identities.get(&name).cloned(),
+        name,
+        major,
+        minor,
+        number: read_value(&path.join("partition")),
+        start: read_sectors(&path.join("start")).ok(),
+        size: read_sectors(&path.join("size"))?,
+        read_only: read_flag(&path.join("ro")).unwrap_or(false),
+        holders: list_dir(&path.join("holders")),
+    })
+}
+
+fn block_device(
+    sysfs: &Path,
+    name: &str,
+    identities: &HashMap<String, FsIdentity>,
+) -> Result<BlockDevice> {
+    let path = sysfs.join("block").join(name);
+    let queue = path.join("queue");
+    let device = path.join("device");
+    let (major, minor) = read_dev(&path.join("dev"))?;
+
+    let mut partitions = Vec::new();
+    for entry in fs::read_dir(&path).map_err(|e| Error::from(e).with_file(&path))? {
+        let entry = entry.map_err(|e| Error::from(e).with_file(&path))?;
+        let entry_path = entry.path();
+        if !entry_path.join("partition").exists() {
+            continue;
+        }
+        if let Some(name) = entry.file_name().to_str() {
+            partitions.push(partition(&entry_path, name, identities)?);
+        }
+    }
+    partitions.sort_by_key(|partition| partition.number);
+
+    let slaves = list_dir(&path.join("slaves"));
+    let mut disks = Vec::new();
+    backing_disks(sysfs, &slaves, &mut disks, 0);
+
+    let name = device_name(name);
+
+    Ok(BlockDevice {
+        identity: identities.get(&name).cloned(),
+        name,
+        major,
+        minor,
+        size: read_sectors(&path.join("size"))?,
+        logical_block_size: read_bytes(&queue.join("logical_block_size")),
+        physical_block_size: read_bytes(&queue.join("physical_block_size")),
+        hw_sector_size: read_bytes(&queue.join("hw_sector_size")),
+        rotational: read_flag(&queue.join("rotational")),
+        removable: read_flag(&path.join("removable")).unwrap_or(false),
+        read_only: read_flag(&path.join("ro")).unwrap_or(false),
+        model: read_string(&device.join("model")),
+        vendor: read_string(&device.join("vendor")),
+        // NVMe controllers expose serial in the `device` directory,
+        // virtio disks in the block device directory itself
+        serial: read_string(&device.join("serial")).or_else(|| read_string(&path.join("serial"))),
+        wwn: read_string(&path.join("wwid")).or_else(|| read_string(&device.join("wwid"))),
+        scheduler: read_string(&queue.join("scheduler"))
+            .as_deref()
+            .and_then(parse_scheduler)
+            .map(str::to_string),
+        queue_depth: read_value(&device.join("queue_depth")),
+        nr_requests: read_value(&queue.join("nr_requests")),
+        partitions,
+        holders: list_dir(&path.join("holders")),
+        slaves,
+        backing_disks: disks,
+    })
+}
+
+pub async fn block_devices() -> Result<impl Stream<Item = Result<BlockDevice>>> {
+    let devices = rt::spawn_blocking(|| {
+        let sysfs = rt::linux::sysfs_root();
+        let path = sysfs.join("block");
+        let identities = read_identities();
+
+        let mut names = Vec::new();
+        for entry in fs::read_dir(&path).map_err(|e| Error::from(e).with_file(&path))? {
+            let entry = entry.map_err(|e| Error::from(e).with_file(&path))?;
+            if let Ok(name) = entry.file_name().into_string() {
+                names.push(name);
+            }
+        }
+        names.sort();
+
+        Ok::<_, Error>(
+            names
+                .iter()
+                .map(|name| block_device(sysfs, name, &identities))
+                .collect::<Vec<_>>(),
+        )
+    })
+    .await?;
+
+    Ok(stream::iter(devices))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{device_name, parse_dev, parse_scheduler};
+
+    #[test]
+    fn test_parse_dev() {
+        assert_eq!(parse_dev("259:0\n").unwrap(), (259, 0));
+        assert!(parse_dev("259").is_err());
+    }
+
+    #[test]
+    fn test_parse_scheduler() {
+        assert_eq!(
+            parse_scheduler("none [mq-deadline] kyber bfq\n"),
+            Some("mq-deadline")
+        );
+        assert_eq!(parse_scheduler("none\n"), Some("none"));
+        assert_eq!(parse_scheduler(""), None);
+    }
+
+    #[test]
+    fn test_device_name() {
+        assert_eq!(device_name("cciss!c0d0"), "cciss/c0d0");
+    }
+}
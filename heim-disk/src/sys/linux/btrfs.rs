@@ -0,0 +1,233 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use heim_common::prelude::*;
+use heim_runtime as rt;
+
+use super::{list_dir, mount_device, read_bytes, read_flag, read_string};
+use crate::os::linux::{
+    BtrfsAllocation, BtrfsDeviceErrors, BtrfsDeviceInfo, BtrfsFilesystem, BtrfsProfile,
+    BtrfsProfileUsage, MountInfo,
+};
+
+fn parse_profile(name: &str) -> BtrfsProfile {
+    match name {
+        "single" => BtrfsProfile::Single,
+        "dup" => BtrfsProfile::Dup,
+        "raid0" => BtrfsProfile::Raid0,
+        "raid1" => BtrfsProfile::Raid1,
+        "raid1c3" => BtrfsProfile::Raid1C3,
+        "raid1c4" => BtrfsProfile::Raid1C4,
+        "raid10" => BtrfsProfile::Raid10,
+        "raid5" => BtrfsProfile::Raid5,
+        "raid6" => BtrfsProfile::Raid6,
+        other => BtrfsProfile::Other(other.to_string()),
+    }
+}
+
+// Example:
+//
+// write_errs 0
+// read_errs 0
+// flush_errs 0
+// corruption_errs 2
+// generation_errs 0
+fn parse_error_stats(contents: &str) -> BtrfsDeviceErrors {
+    let mut errors = BtrfsDeviceErrors::default();
+    for line in contents.lines() {
+        let mut parts = line.split_whitespace();
+        let (key, value) = match (parts.next(), parts.next().and_then(|v| v.parse().ok())) {
+            (Some(key), Some(value)) => (key, value),
+            _ => continue,
+        };
+        match key {
+            "write_errs" => errors.write = value,
+            "read_errs" => errors.read = value,
+            "flush_errs" => errors.flush = value,
+            "corruption_errs" => errors.corruption = value,
+            "generation_errs" => errors.generation = value,
+            _ => continue,
+        }
+    }
+
+    errors
+}
+
+// Profile subdirectories are mixed with the block group type attribute files
+fn read_allocation(path: &Path) -> Option<BtrfsAllocation> {
+    let total = read_bytes(&path.join("total_bytes"))?;
+    let used = read_bytes(&path.join("bytes_used"))?;
+
+    let mut profiles = fs::read_dir(path)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let path = entry.path();
+
+            Some(BtrfsProfileUsage {
+                profile: parse_profile(&name),
+                total: read_bytes(&path.join("total_bytes"))?,
+                used: read_bytes(&path.join("used_bytes"))?,
+            })
+        })
+        .collect::<Vec<_>>();
+    profiles.sort_by_key(|usage| usage.total);
+    profiles.reverse();
+
+    Some(BtrfsAllocation {
+        total,
+        used,
+        disk_total: read_bytes(&path.join("disk_total")),
+        disk_used: read_bytes(&path.join("disk_used")),
+        profiles,
+    })
+}
+
+fn read_device_infos(path: &Path) -> Vec<BtrfsDeviceInfo> {
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(..) => return Vec::new(),
+    };
+
+    let mut infos = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let devid = entry.file_name().to_str()?.parse().ok()?;
+            let path = entry.path();
+
+            Some(BtrfsDeviceInfo {
+                devid,
+                missing: read_flag(&path.join("missing")).unwrap_or(false),
+                writeable: read_flag(&path.join("writeable")).unwrap_or(false),
+                replace_target: read_flag(&path.join("replace_target")).unwrap_or(false),
+                errors: fs::read_to_string(path.join("error_stats"))
+                    .ok()
+                    .map(|contents| parse_error_stats(&contents)),
+            })
+        })
+        .collect::<Vec<_>>();
+    infos.sort_by_key(|info| info.devid);
+
+    infos
+}
+
+fn read_filesystem(path: &Path, uuid: String) -> BtrfsFilesystem {
+    let allocation = path.join("allocation");
+
+    BtrfsFilesystem {
+        uuid,
+        label: read_string(&path.join("label")),
+        node_size: read_bytes(&path.join("nodesize")),
+        sector_size: read_bytes(&path.join("sectorsize")),
+        devices: list_dir(&path.join("devices")),
+        device_infos: read_device_infos(&path.join("devinfo")),
+        data: read_allocation(&allocation.join("data")),
+        metadata: read_allocation(&allocation.join("metadata")),
+        system: read_allocation(&allocation.join("system")),
+    }
+}
+
+// Returns `(uuid, path)` pairs for mounted filesystems, skipping the global `features` directory
+fn list_filesystems(root: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let entries = match fs::read_dir(root) {
+        Ok(entries) => entries,
+        // `btrfs` module is not loaded
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(Error::from(e).with_file(root)),
+    };
+
+    let mut filesystems = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| Error::from(e).with_file(root))?;
+        let path = entry.path();
+        if !path.join("devices").is_dir() {
+            continue;
+        }
+        if let Ok(uuid) = entry.file_name().into_string() {
+            filesystems.push((uuid, path));
+        }
+    }
+    filesystems.sort();
+
+    Ok(filesystems)
+}
+
+pub async fn btrfs_filesystems() -> Result<impl Stream<Item = Result<BtrfsFilesystem>>> {
+    let filesystems = rt::spawn_blocking(|| {
+        let root = rt::linux::sysfs_root().join("fs/btrfs");
+        let filesystems = list_filesystems(&root)?
+            .into_iter()
+            .map(|(uuid, path)| read_filesystem(&path, uuid))
+            .collect::<Vec<_>>();
+
+        Ok::<_, Error>(filesystems)
+    })
+    .await?;
+
+    Ok(stream::iter(filesystems).map(Ok))
+}
+
+pub async fn btrfs_filesystem(
+    info: MountInfo,
+    uuid: Option<String>,
+) -> Result<Option<BtrfsFilesystem>> {
+    rt::spawn_blocking(move || {
+        let sysfs = rt::linux::sysfs_root();
+        let root = sysfs.join("fs/btrfs");
+
+        if let Some(uuid) = uuid.map(|uuid| uuid.to_lowercase()) {
+            let path = root.join(&uuid);
+            if path.join("devices").is_dir() {
+                return Ok(Some(read_filesystem(&path, uuid)));
+            }
+        }
+
+        let device = match mount_device(sysfs, &info) {
+            Some(device) => device,
+            None => return Ok(None),
+        };
+        let filesystem = list_filesystems(&root)?
+            .into_iter()
+            .find(|(_, path)| list_dir(&path.join("devices")).contains(&device))
+            .map(|(uuid, path)| read_filesystem(&path, uuid));
+
+        Ok(filesystem)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_error_stats, parse_profile};
+    use crate::os::linux::BtrfsProfile;
+
+    #[test]
+    fn test_parse_profile() {
+        assert_eq!(parse_profile("raid1c3"), BtrfsProfile::Raid1C3);
+        assert_eq!(parse_profile("dup"), BtrfsProfile::Dup);
+        assert_eq!(
+            parse_profile("raid-stripe"),
+            BtrfsProfile::Other("raid-stripe".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_error_stats() {
+        let errors = parse_error_stats(
+            "write_errs 1
+read_errs 2
+flush_errs 0
+corruption_errs 7
+generation_errs 0
+",
+        );
+        assert_eq!(errors.write(), 1);
+        assert_eq!(errors.read(), 2);
+        assert_eq!(errors.flush(), 0);
+        assert_eq!(errors.corruption(), 7);
+        assert_eq!(errors.generation(), 0);
+    }
+}
@@ -1,4 +1,5 @@
 use std::ffi::{CString, OsStr};
+use std::fs;
 use std::str::FromStr;
 
 use heim_common::prelude::*;
@@ -6,6 +7,9 @@ use heim_common::units::{information, time, Information, Time};
 use heim_common::utils::iter::*;
 use heim_runtime as rt;
 
+use super::read_dm;
+use crate::os::linux::DmDevice;
+
 // Copied from the `psutil` sources:
 //
 // "man iostat" states that sectors are equivalent with blocks and have
@@ -18,18 +22,32 @@ use heim_runtime as rt;
 // * https://github.com/giampaolo/psutil/issues/1305
 // * https://github.com/torvalds/linux/blob/4f671fe2f9523a1ea206f63fe60a7c7b3a56d5c7/include/linux/bio.h#L99
 // * https://lkml.org/lkml/2015/8/17/234
-const DISK_SECTOR_SIZE: u64 = 512;
+//
+// Same units are used for the `/sys/block/{DISK}/size` and partition `start` values,
+// the real sector size is exposed via `os::linux::BlockDevice::hw_sector_size` instead.
+pub const DISK_SECTOR_SIZE: u64 = 512;
 
 #[derive(Debug, Default)]
 pub struct IoCounters {
     name: String,
     read_count: u64,
-    write_count: u64,
+    read_merged_count: u64,
     read_bytes: Information,
+    read_time: Time,
+    write_count: u64,
+    write_merged_count: u64,
     write_bytes: Information,
+    write_time: Time,
+    io_in_progress: u64,
     busy_time: Time,
-    read_merged_count: u64,
-    write_merged_count: u64,
+    weighted_io_time: Time,
+    discard_count: Option<u64>,
+    discard_merged_count: Option<u64>,
+    discard_bytes: Option<Information>,
+    discard_time: Option<Time>,
+    flush_count: Option<u64>,
+    flush_time: Option<Time>,
+    dm: Option<DmDevice>,
 }
 
 impl IoCounters {
@@ -53,8 +71,60 @@ impl IoCounters {
         self.write_bytes
     }
 
-    pub fn busy_time(&self) -> Time {
-        self.busy_time
+    pub fn busy_time(&self) -> Option<Time> {
+        Some(self.busy_time)
+    }
+
+    pub fn read_merged_count(&self) -> Option<u64> {
+        Some(self.read_merged_count)
+    }
+
+    pub fn write_merged_count(&self) -> Option<u64> {
+        Some(self.write_merged_count)
+    }
+
+    pub fn read_time(&self) -> Time {
+        self.read_time
+    }
+
+    pub fn write_time(&self) -> Time {
+        self.write_time
+    }
+
+    pub fn io_in_progress(&self) -> u64 {
+        self.io_in_progress
+    }
+
+    pub fn weighted_io_time(&self) -> Time {
+        self.weighted_io_time
+    }
+
+    pub fn discard_count(&self) -> Option<u64> {
+        self.discard_count
+    }
+
+    pub fn discard_merged_count(&self) -> Option<u64> {
+        self.discard_merged_count
+    }
+
+    pub fn discard_bytes(&self) -> Option<Information> {
+        self.discard_bytes
+    }
+
+    pub fn discard_time(&self) -> Option<Time> {
+        self.discard_time
+    }
+
+    pub fn flush_count(&self) -> Option<u64> {
+        self.flush_count
+    }
+
+    pub fn flush_time(&self) -> Option<Time> {
+        self.flush_time
+    }
+
+    pub fn dm_device(&self) -> Option<&DmDevice> {
+        self.dm.as_ref()
     }
 
     // Based on the sysstat code:
@@ -73,11 +143,19 @@ impl IoCounters {
     }
 }
 
+fn sectors(value: u64) -> Information {
+    Information::new::<information::byte>(value * DISK_SECTOR_SIZE)
+}
+
+fn milliseconds(value: u64) -> Time {
+    Time::new::<time::millisecond>(value as f64)
+}
+
 impl FromStr for IoCounters {
     type Err = Error;
 
-    // At the moment supports format used in Linux 2.6+,
-    // except ignoring discard values introduced in Linux 4.18.
+    // Supports format used in Linux 2.6.25+, including discard fields
+    // introduced in Linux 4.18 and flush fields introduced in Linux 5.5.
     //
     // https://www.kernel.org/doc/Documentation/iostats.txt
     // https://www.kernel.org/doc/Documentation/ABI/testing/procfs-diskstats
@@ -87,38 +165,73 @@ impl FromStr for IoCounters {
         let name: String = parts.try_from_next()?;
         let read_count = parts.try_parse_next()?;
         let read_merged_count = parts.try_parse_next()?;
-        let read_bytes = parts
-            .try_parse_next()
-            .map(|bytes: u64| Information::new::<information::byte>(bytes * DISK_SECTOR_SIZE))?;
-        let mut parts = parts.skip(1);
+        let read_bytes = parts.try_parse_next().map(sectors)?;
+        let read_time = parts.try_parse_next().map(milliseconds)?;
         let write_count = parts.try_parse_next()?;
         let write_merged_count = parts.try_parse_next()?;
-        let write_bytes = parts
-            .try_parse_next()
-            .map(|bytes: u64| Information::new::<information::byte>(bytes * DISK_SECTOR_SIZE))?;
-        let mut parts = parts.skip(2);
-        let busy_time = parts
-            .try_parse_next()
-            .map(|seconds: u64| Time::new::<time::second>(seconds as f64))?;
+        let write_bytes = parts.try_parse_next().map(sectors)?;
+        let write_time = parts.try_parse_next().map(milliseconds)?;
+        let io_in_progress = parts.try_parse_next()?;
+        // `io_ticks` field, expressed in milliseconds
+        let busy_time = parts.try_parse_next().map(milliseconds)?;
+        let weighted_io_time = parts.try_parse_next().map(milliseconds)?;
+
+        // Optional fields are missing in older kernels
+        let mut optional = || parts.next().and_then(|value| value.parse::<u64>().ok());
+        let discard_count = optional();
+        let discard_merged_count = optional();
+        let discard_bytes = optional().map(sectors);
+        let discard_time = optional().map(milliseconds);
+        let flush_count = optional();
+        let flush_time = optional().map(milliseconds);
 
         Ok(IoCounters {
             name,
             read_count,
             read_merged_count,
             read_bytes,
+            read_time,
             write_count,
             write_merged_count,
             write_bytes,
+            write_time,
+            io_in_progress,
             busy_time,
+            weighted_io_time,
+            discard_count,
+            discard_merged_count,
+            discard_bytes,
+            discard_time,
+            flush_count,
+            flush_time,
+            dm: None,
         })
     }
 }
 
 pub async fn io_counters() -> Result<impl Stream<Item = Result<IoCounters>>> {
-    let stream =
-        rt::fs::read_lines_into::<_, _, Error>(rt::linux::procfs_root().join("diskstats")).await?;
+    let counters = rt::spawn_blocking(|| {
+        let path = rt::linux::procfs_root().join("diskstats");
+        let contents = fs::read_to_string(&path).map_err(|e| Error::from(e).with_file(&path))?;
+        let sysfs = rt::linux::sysfs_root();
 
-    Ok(stream)
+        let counters = contents
+            .lines()
+            .map(|line| {
+                let mut counters = IoCounters::from_str(line)?;
+                if counters.name.starts_with("dm-") {
+                    counters.dm = read_dm(sysfs, &counters.name);
+                }
+
+                Ok(counters)
+            })
+            .collect::<Vec<_>>();
+
+        Ok::<_, Error>(counters)
+    })
+    .await?;
+
+    Ok(stream::iter(counters))
 }
 
 pub async fn io_counters_physical() -> Result<impl Stream<Item = Result<IoCounters>>> {
@@ -133,3 +246,48 @@ pub async fn io_counters_physical() -> Result<impl Stream<Item = Result<IoCounte
 
     Ok(stream)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use heim_common::units::{information, time};
+
+    use super::IoCounters;
+
+    #[test]
+    fn test_parse_diskstats() {
+        // Linux 5.5+ format with discard and flush fields
+        let line = " 259       0 nvme0n1 3497 1024 410232 1468 9283 5577 1155386 15330 2 12628 18210 12 0 2048 5 451 1407";
+        let counters = IoCounters::from_str(line).unwrap();
+
+        assert_eq!(counters.read_count(), 3497);
+        assert_eq!(counters.read_merged_count(), Some(1024));
+        assert_eq!(
+            counters.read_bytes().get::<information::byte>(),
+            410_232 * 512
+        );
+        assert_eq!(counters.io_in_progress(), 2);
+        assert_eq!(
+            counters.busy_time().unwrap().get::<time::millisecond>(),
+            12628.0
+        );
+        assert_eq!(
+            counters.weighted_io_time().get::<time::millisecond>(),
+            18210.0
+        );
+        assert_eq!(
+            counters.discard_bytes().unwrap().get::<information::byte>(),
+            2048 * 512
+        );
+        assert_eq!(counters.flush_count(), Some(451));
+
+        // Linux 2.6.25 - 4.17 format
+        let line = "   8       0 sda 3497 1024 410232 1468 9283 5577 1155386 15330 0 12628 18210";
+        let counters = IoCounters::from_str(line).unwrap();
+
+        assert_eq!(counters.write_merged_count(), Some(5577));
+        assert_eq!(counters.discard_count(), None);
+        assert_eq!(counters.flush_time(), None);
+    }
+}
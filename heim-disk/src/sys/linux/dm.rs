@@ -0,0 +1,140 @@
+use std::fs;
+use std::path::Path;
+
+use heim_common::prelude::*;
+use heim_runtime as rt;
+
+use super::mount_device;
+use crate::os::linux::{DmDevice, DmKind, MountInfo};
+
+fn parse_dm_kind(uuid: &str) -> Option<DmKind> {
+    let kind = if uuid.starts_with("LVM-") {
+        DmKind::Lvm
+    } else if uuid.starts_with("CRYPT-") {
+        DmKind::Crypt
+    } else if uuid.starts_with("mpath-") {
+        DmKind::Multipath
+    } else if uuid.starts_with("part") && uuid.contains('-') {
+        // `kpartx` uses `part<N>-<parent uuid>` format
+        DmKind::Partition
+    } else {
+        match uuid.find('-') {
+            Some(idx) if idx > 0 => DmKind::Other(uuid[..idx].to_string()),
+            _ => return None,
+        }
+    };
+
+    Some(kind)
+}
+
+// LVM joins volume group and logical volume names with `-`,
+// escaping `-` inside of the names as `--`, ex. `my--vg-my--lv`
+fn split_lvm_name(name: &str) -> Option<(String, String)> {
+    let bytes = name.as_bytes();
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if bytes[idx] == b'-' {
+            if bytes.get(idx + 1) == Some(&b'-') {
+                idx += 2;
+                continue;
+            }
+
+            let vg = name[..idx].replace("--", "-");
+            let lv = name[idx + 1..].replace("--", "-");
+            return Some((vg, lv));
+        }
+        idx += 1;
+    }
+
+    None
+}
+
+fn read_line(path: &Path) -> Option<String> {
+    let contents = fs::read_to_string(path).ok()?;
+    let value = contents.trim();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+// `device_name` is a kernel name, ex. `dm-3`
+pub fn read_dm(sysfs: &Path, device_name: &str) -> Option<DmDevice> {
+    if !device_name.starts_with("dm-") {
+        return None;
+    }
+
+    let root = sysfs.join("block").join(device_name).join("dm");
+    let name = read_line(&root.join("name"))?;
+    let uuid = read_line(&root.join("uuid"));
+    let kind = uuid.as_deref().and_then(parse_dm_kind);
+    let lvm_names = match kind {
+        Some(DmKind::Lvm) => split_lvm_name(&name),
+        _ => None,
+    };
+
+    Some(DmDevice {
+        device_name: device_name.to_string(),
+        mapper_path: rt::linux::devfs_root().join("mapper").join(&name),
+        name,
+        uuid,
+        kind,
+        lvm_names,
+    })
+}
+
+// Finds device-mapper device for the mount; `/dev/mapper` sources are also looked up
+// by their names, since `/dev` might be not available in containers
+pub fn dm_for_mount(sysfs: &Path, info: &MountInfo) -> Option<DmDevice> {
+    if let Some(name) = mount_device(sysfs, info) {
+        return read_dm(sysfs, &name);
+    }
+
+    let dm_name = info.source()?.strip_prefix("/dev/mapper/")?;
+    fs::read_dir(sysfs.join("block"))
+        .ok()?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter_map(|name| read_dm(sysfs, &name))
+        .find(|device| device.name() == dm_name)
+}
+
+pub async fn mount_dm_device(info: MountInfo) -> Result<Option<DmDevice>> {
+    let device = rt::spawn_blocking(move || dm_for_mount(rt::linux::sysfs_root(), &info)).await;
+
+    Ok(device)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_dm_kind, split_lvm_name};
+    use crate::os::linux::DmKind;
+
+    #[test]
+    fn test_parse_dm_kind() {
+        assert_eq!(parse_dm_kind("LVM-abcdefgh12345678"), Some(DmKind::Lvm));
+        assert_eq!(
+            parse_dm_kind("CRYPT-LUKS2-0123456789abcdef-luks"),
+            Some(DmKind::Crypt)
+        );
+        assert_eq!(parse_dm_kind("part1-mpath-3600"), Some(DmKind::Partition));
+        assert_eq!(
+            parse_dm_kind("stratis-1-private"),
+            Some(DmKind::Other("stratis".to_string()))
+        );
+        assert_eq!(parse_dm_kind("nouuid"), None);
+    }
+
+    #[test]
+    fn test_split_lvm_name() {
+        assert_eq!(
+            split_lvm_name("vg0-root"),
+            Some(("vg0".to_string(), "root".to_string()))
+        );
+        assert_eq!(
+            split_lvm_name("my--vg-my--lv"),
+            Some(("my-vg".to_string(), "my-lv".to_string()))
+        );
+        assert_eq!(split_lvm_name("plain"), None);
+    }
+}
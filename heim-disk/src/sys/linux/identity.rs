@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use heim_common::prelude::*;
+use heim_runtime as rt;
+
+use super::{device_name, mount_device};
+use crate::os::linux::{FsIdentity, MountInfo};
+
+// udev escapes unsafe characters in the link names as `\xHH`, ex. `My\x20Disk`.
+// See `encode_devnode_name` in the systemd sources.
+fn decode_udev_name(name: &str) -> String {
+    let bytes = name.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let decoded = match bytes.get(idx..idx + 4) {
+            Some([b'\\', b'x', high, low]) => std::str::from_utf8(&[*high, *low])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+
+        match decoded {
+            Some(byte) => {
+                result.push(byte);
+                idx += 4;
+            }
+            None => {
+                result.push(bytes[idx]);
+                idx += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&result).into_owned()
+}
+
+// Returns `(link name, device name)` pairs from one of the `/dev/disk/by-*` directories
+fn read_links(path: &Path) -> Vec<(String, String)> {
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(..) => return Vec::new(),
+    };
+
+    entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let link = entry.file_name().into_string().ok()?;
+            let target = fs::canonicalize(entry.path()).ok()?;
+            let name = target.file_name()?.to_str()?;
+
+            Some((decode_udev_name(&link), device_name(name)))
+        })
+        .collect()
+}
+
+type Setter = fn(&mut FsIdentity, String);
+
+// Returns identities keyed by the block device name
+pub fn read_identities() -> HashMap<String, FsIdentity> {
+    let root = rt::linux::devfs_root().join("disk");
+    let mut identities: HashMap<String, FsIdentity> = HashMap::new();
+
+    let fields: [(&str, Setter); 4] = [
+        ("by-uuid", |identity, value| identity.uuid = Some(value)),
+        ("by-label", |identity, value| identity.label = Some(value)),
+        ("by-partuuid", |identity, value| {
+            identity.part_uuid = Some(value)
+        }),
+        ("by-partlabel", |identity, value| {
+            identity.part_label = Some(value)
+        }),
+    ];
+    for (dir, set) in fields.iter() {
+        for (link, name) in read_links(&root.join(dir)) {
+            let identity = identities
+                .entry(name.clone())
+                .or_insert_with(|| FsIdentity {
+                    device_name: name,
+                    ..FsIdentity::default()
+                });
+            set(identity, link);
+        }
+    }
+
+    identities
+}
+
+pub async fn fs_identities() -> Result<impl Stream<Item = Result<FsIdentity>>> {
+    let mut identities =
+        rt::spawn_blocking(|| read_identities().values().cloned().collect::<Vec<_>>()).await;
+    identities.sort_by(|a, b| a.device_name.cmp(&b.device_name));
+
+    Ok(stream::iter(identities).map(Ok))
+}
+
+pub async fn fs_identity_by_uuid(uuid: &str) -> Result<Option<FsIdentity>> {
+    let uuid = uuid.to_string();
+
+    let identity = rt::spawn_blocking(move || {
+        read_identities()
+            .into_iter()
+            .find_map(|(_, identity)| match &identity.uuid {
+                Some(value) if value.eq_ignore_ascii_case(&uuid) => Some(identity),
+                _ => None,
+            })
+    })
+    .await;
+
+    Ok(identity)
+}
+
+pub async fn mount_fs_identity(info: MountInfo) -> Result<Option<FsIdentity>> {
+    let identity = rt::spawn_blocking(move || {
+        let name = mount_device(rt::linux::sysfs_root(), &info)?;
+
+        read_identities().remove(&name)
+    })
+    .await;
+
+    Ok(identity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decode_udev_name;
+
+    #[test]
+    fn test_decode_udev_name() {
+        assert_eq!(decode_udev_name("My\\x20Disk"), "My Disk");
+        assert_eq!(decode_udev_name("EFI\\x2fBOOT"), "EFI/BOOT");
+        assert_eq!(decode_udev_name("plain\\x"), "plain\\x");
+    }
+}
@@ -0,0 +1,304 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use heim_common::prelude::*;
+use heim_common::units::{information, information_rate, time, Information, InformationRate, Time};
+use heim_runtime as rt;
+
+use crate::os::linux::{MdArray, MdMember, MdSync, MdSyncAction};
+
+fn kibibytes(value: u64) -> Information {
+    Information::new::<information::kibibyte>(value)
+}
+
+// Example: `sdc1[1](F)`, flags are documented in the `md_seq_show` kernel function
+fn parse_member(token: &str) -> Option<MdMember> {
+    let open = token.find('[')?;
+    let close = token.find(']')?;
+    let descriptor = token.get(open + 1..close)?.parse().ok()?;
+    let flags = &token[close + 1..];
+
+    Some(MdMember {
+        name: token[..open].to_string(),
+        descriptor,
+        slot: None,
+        faulty: flags.contains("(F)"),
+        spare: flags.contains("(S)"),
+        write_mostly: flags.contains("(W)"),
+        replacement: flags.contains("(R)"),
+        state: Vec::new(),
+    })
+}
+
+// Example: `md1 : active (auto-read-only) raid5 sdd1[3] sdc1[1](F) sdb2[0]`
+fn parse_header(name: &str, tokens: &[&str]) -> MdArray {
+    let mut array = MdArray {
+        name: name.to_string(),
+        active: false,
+        read_only: false,
+        level: None,
+        array_state: None,
+        size: None,
+        raid_disks: None,
+        active_disks: None,
+        degraded: None,
+        members: Vec::new(),
+        sync: None,
+    };
+
+    for token in tokens {
+        match *token {
+            "active" => array.active = true,
+            "inactive" => array.active = false,
+            "(read-only)" | "(auto-read-only)" => array.read_only = true,
+            token if token.contains('[') => array.members.extend(parse_member(token)),
+            token if !token.starts_with('(') => array.level = Some(token.to_string()),
+            _ => continue,
+        }
+    }
+
+    array
+}
+
+// Example: `2093056 blocks super 1.2 level 5, 512k chunk, algorithm 2 [3/2] [U_U]`
+fn parse_status(array: &mut MdArray, tokens: &[&str]) {
+    if let [blocks, "blocks", ..] = tokens {
+        array.size = blocks.parse().ok().map(kibibytes);
+    }
+
+    let disks = tokens.iter().find_map(|token| {
+        let mut disks = token.strip_prefix('[')?.strip_suffix(']')?.splitn(2, '/');
+        let raid_disks = disks.next()?.parse::<u32>().ok()?;
+        let active_disks = disks.next()?.parse::<u32>().ok()?;
+
+        Some((raid_disks, active_disks))
+    });
+    if let Some((raid_disks, active_disks)) = disks {
+        array.raid_disks = Some(raid_disks);
+        array.active_disks = Some(active_disks);
+        array.degraded = Some(raid_disks.saturating_sub(active_disks));
+    }
+}
+
+// Examples:
+//
+// [=>...................]  recovery =  8.5% (89600/1046528) finish=0.7min speed=22400K/sec
+// resync=DELAYED
+fn parse_sync(tokens: &[&str]) -> Option<MdSync> {
+    let (action, value) = tokens.iter().find_map(|token| {
+        let mut parts = token.splitn(2, '=');
+        let action = match parts.next()? {
+            "resync" => MdSyncAction::Resync,
+            "recovery" => MdSyncAction::Recovery,
+            "reshape" => MdSyncAction::Reshape,
+            "check" => MdSyncAction::Check,
+            "repair" => MdSyncAction::Repair,
+            _ => return None,
+        };
+
+        Some((action, parts.next().unwrap_or("")))
+    })?;
+
+    let mut sync = MdSync {
+        action,
+        completed: None,
+        total: None,
+        speed: None,
+        remaining: None,
+        delayed: value == "DELAYED" || value == "PENDING",
+    };
+
+    for token in tokens {
+        if let Some(progress) = token.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+            let mut parts = progress.splitn(2, '/');
+            sync.completed = parts.next().and_then(|v| v.parse().ok()).map(kibibytes);
+            sync.total = parts.next().and_then(|v| v.parse().ok()).map(kibibytes);
+        } else if let Some(finish) = token.strip_prefix("finish=") {
+            sync.remaining = finish
+                .strip_suffix("min")
+                .and_then(|v| v.parse::<f64>().ok())
+                .map(Time::new::<time::minute>);
+        } else if let Some(speed) = token.strip_prefix("speed=") {
+            sync.speed = speed
+                .strip_suffix("K/sec")
+                .and_then(|v| v.parse::<f64>().ok())
+                .map(InformationRate::new::<information_rate::kibibyte_per_second>);
+        }
+    }
+
+    Some(sync)
+}
+
+// Example:
+//
+// Personalities : [raid1] [raid6] [raid5] [raid4]
+// md1 : active raid5 sdd1[3] sdc1[1](F) sdb2[0] sde1[4](S)
+//       2093056 blocks super 1.2 level 5, 512k chunk, algorithm 2 [3/2] [U_U]
+//       [=>...................]  recovery =  8.5% (89600/1046528) finish=0.7min speed=22400K/sec
+//       bitmap: 0/1 pages [0KB], 65536KB chunk
+//
+// unused devices: <none>
+pub fn parse_mdstat(contents: &str) -> Vec<MdArray> {
+    let mut arrays: Vec<MdArray> = Vec::new();
+
+    for line in contents.lines() {
+        let tokens = line.split_whitespace().collect::<Vec<_>>();
+        if !line.starts_with(char::is_whitespace) {
+            match tokens.as_slice() {
+                ["Personalities", ..] => continue,
+                [name, ":", rest @ ..] => arrays.push(parse_header(name, rest)),
+                _ => continue,
+            }
+            continue;
+        }
+
+        let array = match arrays.last_mut() {
+            Some(array) => array,
+            None => continue,
+        };
+        if tokens.get(1) == Some(&"blocks") {
+            parse_status(array, &tokens);
+        } else if let Some(sync) = parse_sync(&tokens) {
+            array.sync = Some(sync);
+        }
+    }
+
+    arrays
+}
+
+fn read_string(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|contents| contents.trim().to_string())
+}
+
+// Enriches array with the data from the `/sys/block/md*/md/` directory, if it is available
+fn read_sysfs(array: &mut MdArray, sysfs: &Path) {
+    let root = sysfs.join("block").join(&array.name).join("md");
+
+    if let Some(level) = read_string(&root.join("level")).filter(|level| !level.is_empty()) {
+        array.level = Some(level);
+    }
+    if let Some(state) = read_string(&root.join("array_state")) {
+        array.array_state = Some(state);
+    }
+    if let Some(disks) = read_string(&root.join("raid_disks")).and_then(|v| v.parse().ok()) {
+        array.raid_disks = Some(disks);
+    }
+    if let Some(degraded) = read_string(&root.join("degraded")).and_then(|v| v.parse().ok()) {
+        array.degraded = Some(degraded);
+    }
+
+    for member in array.members.iter_mut() {
+        let dev = root.join(format!("dev-{}", member.name));
+        // `slot` is `none` for spare and faulty devices
+        member.slot = read_string(&dev.join("slot")).and_then(|slot| slot.parse().ok());
+        if let Some(state) = read_string(&dev.join("state")) {
+            member.state = state
+                .split(',')
+                .filter(|flag| !flag.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+
+        let state = &member.state;
+        let has = |flag: &str| state.iter().any(|state| state == flag);
+        let (faulty, spare) = (has("faulty"), has("spare"));
+        let (write_mostly, replacement) = (has("write_mostly"), has("replacement"));
+        member.faulty |= faulty;
+        member.spare |= spare;
+        member.write_mostly |= write_mostly;
+        member.replacement |= replacement;
+    }
+}
+
+pub async fn md_arrays() -> Result<impl Stream<Item = Result<MdArray>>> {
+    let arrays = rt::spawn_blocking(|| {
+        let path = rt::linux::procfs_root().join("mdstat");
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            // `md` driver is not loaded
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(Error::from(e).with_file(path)),
+        };
+
+        let mut arrays = parse_mdstat(&contents);
+        for array in arrays.iter_mut() {
+            read_sysfs(array, rt::linux::sysfs_root());
+        }
+
+        Ok(arrays)
+    })
+    .await?;
+
+    Ok(stream::iter(arrays.into_iter().map(Ok)))
+}
+
+#[cfg(test)]
+mod tests {
+    use heim_common::units::{information, information_rate, ratio, time};
+
+    use super::parse_mdstat;
+    use crate::os::linux::MdSyncAction;
+
+    #[test]
+    fn test_parse_mdstat() {
+        let arrays = parse_mdstat(
+            "Personalities : [raid1] [raid6] [raid5] [raid4]
+md0 : active (auto-read-only) raid1 sdb1[1] sda1[0]
+      1046528 blocks super 1.2 [2/2] [UU]
+      \tresync=PENDING
+
+md1 : active raid5 sdd1[3] sdc1[1](F) sdb2[0] sde1[4](S)
+      2093056 blocks super 1.2 level 5, 512k chunk, algorithm 2 [3/2] [U_U]
+      [=>...................]  recovery =  8.5% (89600/1046528) finish=0.7min speed=22400K/sec
+      bitmap: 0/1 pages [0KB], 65536KB chunk
+
+md2 : inactive sdf1[0](S)
+      1046528 blocks super 1.2
+
+unused devices: <none>
+",
+        );
+        assert_eq!(arrays.len(), 3);
+
+        let md0 = &arrays[0];
+        assert!(md0.is_active());
+        assert!(md0.is_read_only());
+        assert_eq!(md0.level(), Some("raid1"));
+        assert!(!md0.is_degraded());
+        assert_eq!(md0.members().len(), 2);
+        let sync = md0.sync().unwrap();
+        assert_eq!(sync.action(), MdSyncAction::Resync);
+        assert!(sync.is_delayed());
+        assert!(sync.progress().is_none());
+
+        let md1 = &arrays[1];
+        assert_eq!(
+            md1.size().unwrap().get::<information::kibibyte>(),
+            2_093_056
+        );
+        assert_eq!(md1.raid_disks(), Some(3));
+        assert_eq!(md1.degraded(), Some(1));
+        assert!(md1.members()[1].is_faulty());
+        assert!(md1.members()[3].is_spare());
+        assert_eq!(md1.members()[3].descriptor(), 4);
+        let sync = md1.sync().unwrap();
+        assert_eq!(sync.action(), MdSyncAction::Recovery);
+        assert!((sync.progress().unwrap().get::<ratio::ratio>() - 0.0856).abs() < 0.001);
+        assert_eq!(sync.remaining().unwrap().get::<time::minute>(), 0.7);
+        assert_eq!(
+            sync.speed()
+                .unwrap()
+                .get::<information_rate::kibibyte_per_second>(),
+            22400.0
+        );
+
+        let md2 = &arrays[2];
+        assert!(!md2.is_active());
+        assert_eq!(md2.level(), None);
+        assert_eq!(md2.degraded(), None);
+        assert!(md2.sync().is_none());
+    }
+}
@@ -1,5 +1,21 @@
+mod backing;
+mod block;
+mod btrfs;
 mod counters;
+mod dm;
+mod identity;
+mod md;
+mod mountinfo;
 mod partitions;
+mod watch;
 
+pub use self::backing::*;
+pub use self::block::*;
+pub use self::btrfs::*;
 pub use self::counters::*;
+pub use self::dm::*;
+pub use self::identity::*;
+pub use self::md::*;
+pub use self::mountinfo::*;
 pub use self::partitions::*;
+pub use self::watch::*;
@@ -0,0 +1,167 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use heim_common::prelude::*;
+use heim_common::sys::linux::unescape_octal;
+use heim_runtime as rt;
+
+use super::device_name;
+use crate::os::linux::{MountInfo, MountTable};
+use crate::FileSystem;
+
+impl FromStr for MountInfo {
+    type Err = Error;
+
+    // Example:
+    //
+    // 36 35 98:0 /mnt1 /mnt2 rw,noatime master:1 - ext3 /dev/root rw,errors=continue
+    //
+    // Number of optional fields before the `-` separator varies.
+    fn from_str(line: &str) -> Result<MountInfo> {
+        let invalid = || {
+            let inner = io::Error::from(io::ErrorKind::InvalidData);
+            Error::from(inner).with_message(format!("Invalid mountinfo line: {}", line))
+        };
+
+        let parts = line.split(' ').collect::<Vec<_>>();
+        let separator = match parts.iter().skip(6).position(|part| *part == "-") {
+            Some(idx) => idx + 6,
+            None => return Err(invalid()),
+        };
+
+        match (&parts[..separator], &parts[separator + 1..]) {
+            (
+                [mount_id, parent_id, device, root, mount_point, mount_options, optional_fields @ ..],
+                [file_system, source, super_options, ..],
+            ) => {
+                let mut device = device.splitn(2, ':');
+                let (major, minor) = match (device.next(), device.next()) {
+                    (Some(major), Some(minor)) => (major.parse()?, minor.parse()?),
+                    _ => return Err(invalid()),
+                };
+                let source = match unescape_octal(source) {
+                    source if source.is_empty() || source == "none" => None,
+                    source => Some(source.into_owned()),
+                };
+
+                Ok(MountInfo {
+                    mount_id: mount_id.parse()?,
+                    parent_id: parent_id.parse()?,
+                    major,
+                    minor,
+                    root: PathBuf::from(unescape_octal(root).as_ref()),
+                    mount_point: PathBuf::from(unescape_octal(mount_point).as_ref()),
+                    mount_options: mount_options.to_string(),
+                    optional_fields: optional_fields.iter().map(|f| f.to_string()).collect(),
+                    file_system: FileSystem::from_str(&unescape_octal(file_system))?,
+                    source,
+                    super_options: super_options.to_string(),
+                })
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+// Lines which can't be parsed are skipped, same as `partitions` always did,
+// so one unusual mount does not hide all the others.
+pub fn parse_mountinfo(contents: &str) -> Vec<MountInfo> {
+    contents
+        .lines()
+        .filter_map(|line| MountInfo::from_str(line).ok())
+        .collect()
+}
+
+pub fn read_mountinfo() -> Result<Vec<MountInfo>> {
+    let path = rt::linux::procfs_root().join("self/mountinfo");
+    let contents = fs::read_to_string(&path).map_err(|e| Error::from(e).with_file(&path))?;
+
+    Ok(parse_mountinfo(&contents))
+}
+
+// Returns name of the block device backing the mount, trying `major:minor` numbers first
+// and falling back to the mount source, since btrfs mounts have anonymous device numbers.
+pub fn mount_device(sysfs: &Path, info: &MountInfo) -> Option<String> {
+    let link = sysfs.join(format!("dev/block/{}:{}", info.major(), info.minor()));
+    if let Ok(path) = fs::canonicalize(link) {
+        return path.file_name()?.to_str().map(device_name);
+    }
+
+    let source = info.source()?.strip_prefix("/dev/")?;
+    let path = fs::canonicalize(rt::linux::devfs_root().join(source)).ok()?;
+    let name = path.file_name()?.to_str()?;
+    if sysfs.join("class/block").join(name).exists() {
+        Some(device_name(name))
+    } else {
+        None
+    }
+}
+
+pub async fn mount_table() -> Result<MountTable> {
+    let mounts = rt::spawn_blocking(read_mountinfo).await?;
+
+    Ok(MountTable { mounts })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::parse_mountinfo;
+    use crate::os::linux::MountTable;
+    use crate::FileSystem;
+
+    const MOUNTINFO: &str = r"22 1 8:1 / / rw,relatime shared:1 - ext4 /dev/sda1 rw,errors=remount-ro
+23 22 0:21 / /proc rw,nosuid,nodev,noexec,relatime shared:12 - proc proc rw
+40 22 8:17 / /mnt/my\040disk rw,relatime shared:30 master:2 - xfs /dev/sdb1 rw,attr2
+41 22 8:17 /data/www /var/www rw,relatime - xfs /dev/sdb1 rw,attr2
+";
+
+    #[test]
+    fn test_parse_mountinfo() {
+        let mounts = parse_mountinfo(MOUNTINFO);
+        assert_eq!(mounts.len(), 4);
+
+        let root = &mounts[0];
+        assert_eq!(root.mount_id(), 22);
+        assert_eq!(root.parent_id(), 1);
+        assert_eq!((root.major(), root.minor()), (8, 1));
+        assert_eq!(root.shared(), Some(1));
+        assert_eq!(root.file_system(), &FileSystem::Ext4);
+        assert_eq!(root.source(), Some("/dev/sda1"));
+        assert_eq!(root.super_options(), "rw,errors=remount-ro");
+
+        let disk = &mounts[2];
+        assert_eq!(disk.mount_point(), Path::new("/mnt/my disk"));
+        assert_eq!(disk.optional_fields().len(), 2);
+        assert_eq!(disk.master(), Some(2));
+
+        // No optional fields
+        assert!(mounts[3].optional_fields().is_empty());
+        assert!(parse_mountinfo("22 1 8:1 / / rw,relatime ext4 /dev/sda1 rw").is_empty());
+
+        // Invalid lines are skipped
+        let contents = format!("{}22 1 8:1 / / rw,relatime ext4 /dev/sda1 rw\n", MOUNTINFO);
+        assert_eq!(parse_mountinfo(&contents).len(), 4);
+    }
+
+    #[test]
+    fn test_mount_table() {
+        let table = MountTable {
+            mounts: parse_mountinfo(MOUNTINFO),
+        };
+        let root = table.get(22).unwrap();
+        assert!(table.parent(root).is_none());
+        assert_eq!(table.children(root).count(), 3);
+
+        let www = table.get(41).unwrap();
+        assert_eq!(table.bind_source(www).unwrap().mount_id(), 40);
+        assert_eq!(
+            table.bind_source_path(www).unwrap(),
+            Path::new("/mnt/my disk/data/www")
+        );
+        assert!(table.bind_source(table.get(40).unwrap()).is_none());
+    }
+}
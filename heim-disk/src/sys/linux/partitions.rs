@@ -2,93 +2,64 @@ use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::fs;
 use std::io::{self, BufRead};
-use std::path::{Path, PathBuf};
+use std::path::Path;
 use std::str::FromStr;
 
 use heim_common::prelude::*;
 use heim_runtime as rt;
 
+use super::read_mountinfo;
+use crate::os::linux::MountInfo;
 use crate::FileSystem;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Partition {
-    device: Option<String>,
-    mount_point: PathBuf,
-    fs_type: FileSystem,
-    options: String,
+    info: MountInfo,
 }
 
 impl Partition {
     pub fn device(&self) -> Option<&OsStr> {
-        self.device
-            .as_ref()
-            .map(|device| OsStr::new(device.as_str()))
+        self.info.source().map(OsStr::new)
     }
 
     pub fn mount_point(&self) -> &Path {
-        self.mount_point.as_path()
+        self.info.mount_point()
     }
 
     pub fn file_system(&self) -> &FileSystem {
-        &self.fs_type
+        self.info.file_system()
     }
 
     pub fn options(&self) -> &str {
-        &self.options
+        self.info.mount_options()
     }
-}
 
-impl FromStr for Partition {
-    type Err = Error;
-
-    fn from_str(line: &str) -> Result<Partition> {
-        // Example: `/dev/sda3 /home ext4 rw,relatime,data=ordered 0 0`
-        let mut parts = line.splitn(5, ' ');
-        let mount_root = rt::linux::procfs_root().join("mounts");
-        let device = match parts.next() {
-            Some(device) if device == "none" => None,
-            Some(device) => Some(device.to_string()),
-            None => {
-                return Err(Error::missing_key(
-                    "device",
-                    format!("{}", mount_root.display()),
-                ))
-            }
-        };
-        let mount_point = match parts.next() {
-            Some(point) => PathBuf::from(point),
-            None => {
-                return Err(Error::missing_key(
-                    "mount point",
-                    format!("{}", mount_root.display()),
-                ))
-            }
-        };
-        let fs_type = match parts.next() {
-            Some(fs) => FileSystem::from_str(fs)?,
-            _ => {
-                return Err(Error::missing_key(
-                    "file-system type",
-                    format!("{}", mount_root.display()),
-                ))
-            }
-        };
-        let options = match parts.next() {
-            Some(opts) => opts.to_string(),
-            None => {
-                return Err(Error::missing_key(
-                    "options",
-                    format!("{}", mount_root.display()),
-                ))
-            }
-        };
+    pub fn mount_info(&self) -> &MountInfo {
+        &self.info
+    }
 
-        Ok(Partition {
-            device,
-            mount_point,
-            fs_type,
-            options,
-        })
+    // Mount IDs might be reused after unmount, so the rest of the fields
+    // which can't be changed by remount are compared too
+    pub fn is_same_mount(&self, other: &Partition) -> bool {
+        let (this, other) = (&self.info, &other.info);
+
+        this.mount_id == other.mount_id
+            && this.major == other.major
+            && this.minor == other.minor
+            && this.root == other.root
+            && this.mount_point == other.mount_point
+            && this.file_system == other.file_system
+    }
+
+    pub fn is_remounted(&self, other: &Partition) -> bool {
+        self.info.mount_options != other.info.mount_options
+            || self.info.super_options != other.info.super_options
+    }
+}
+
+impl From<MountInfo> for Partition {
+    fn from(info: MountInfo) -> Partition {
+        Partition { info }
     }
 }
 
@@ -124,26 +95,39 @@ async fn known_filesystems() -> Result<HashSet<FileSystem>> {
 }
 
 pub async fn partitions() -> Result<impl Stream<Item = Result<Partition>>> {
-    let lines = rt::fs::read_lines(rt::linux::procfs_root().join("mounts")).await?;
-    let stream = lines
-        .map_err(Error::from)
-        .try_filter_map(|line| async move {
-            let result = Partition::from_str(&line).ok();
+    let partitions = rt::spawn_blocking(|| {
+        let partitions = read_mountinfo()?
+            .into_iter()
+            .map(Partition::from)
+            .collect::<Vec<_>>();
 
-            Ok(result)
-        });
+        Ok::<_, Error>(partitions)
+    })
+    .await?;
 
-    Ok(stream)
+    Ok(stream::iter(partitions).map(Ok))
 }
 
 pub async fn partitions_physical() -> Result<impl Stream<Item = Result<Partition>>> {
     let filesystems = known_filesystems().await?;
     let stream = partitions().await?;
 
-    let stream = stream.try_filter_map(move |part| match part {
-        Partition { device: None, .. } => future::ok(None),
-        Partition { ref fs_type, .. } if !filesystems.contains(fs_type) => future::ok(None),
-        partition => future::ok(Some(partition)),
+    // `/proc/filesystems` is consulted only for the filesystems unknown to `FileSystem`,
+    // since its `nodev` flags are not reliable for the network ones
+    let stream = stream.try_filter_map(move |part| {
+        let fs = part.file_system();
+        let is_physical = match fs {
+            FileSystem::Other(..) => {
+                !fs.is_network() && !fs.is_pseudo() && filesystems.contains(fs)
+            }
+            _ => fs.is_physical(),
+        };
+
+        if part.device().is_none() || !is_physical {
+            future::ok(None)
+        } else {
+            future::ok(Some(part))
+        }
     });
 
     Ok(stream)
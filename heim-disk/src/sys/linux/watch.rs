@@ -0,0 +1,65 @@
+use std::fs;
+use std::io;
+use std::os::unix::io::AsRawFd;
+
+use heim_common::prelude::*;
+use heim_runtime as rt;
+
+// Waiting is split into the short rounds, so the blocking thread and the file
+// are released soon after the changes stream is dropped.
+const WAIT_TIMEOUT_MS: libc::c_int = 1_000;
+
+// Kernel marks the `/proc/self/mountinfo` file description with `POLLPRI | POLLERR`
+// each time when mount namespace changes, see `mounts_poll` in the `fs/proc_namespace.c`.
+// Pending event is reset by the `poll` call itself.
+//
+// Returns `false` if nothing has changed during the wait round.
+fn wait(file: &fs::File) -> Result<bool> {
+    let mut fd = libc::pollfd {
+        fd: file.as_raw_fd(),
+        events: libc::POLLPRI,
+        revents: 0,
+    };
+
+    loop {
+        // SAFETY: `fd` is a valid `pollfd` struct referring to the descriptor owned by `file`,
+        // which outlives the call.
+        let result = unsafe { libc::poll(&mut fd, 1, WAIT_TIMEOUT_MS) };
+        if result >= 0 {
+            return Ok(result > 0);
+        }
+
+        let e = io::Error::last_os_error();
+        if e.kind() != io::ErrorKind::Interrupted {
+            return Err(Error::from(e).with_ffi("poll"));
+        }
+    }
+}
+
+pub async fn mount_changes() -> Result<impl Stream<Item = Result<()>>> {
+    let path = rt::linux::procfs_root().join("self/mountinfo");
+    let file = rt::spawn_blocking(move || {
+        fs::File::open(&path).map_err(|e| Error::from(e).with_file(&path))
+    })
+    .await?;
+
+    let stream = stream::unfold(Some(file), |file| async move {
+        let mut file = file?;
+        loop {
+            let (returned, result) = rt::spawn_blocking(move || {
+                let result = wait(&file);
+                (file, result)
+            })
+            .await;
+            file = returned;
+
+            match result {
+                Ok(true) => return Some((Ok(()), Some(file))),
+                Ok(false) => continue,
+                Err(e) => return Some((Err(e), None)),
+            }
+        }
+    });
+
+    Ok(stream)
+}
@@ -44,6 +44,18 @@ impl IoCounters {
     pub fn write_time(&self) -> Time {
         self.write_time
     }
+
+    pub fn busy_time(&self) -> Option<Time> {
+        None
+    }
+
+    pub fn read_merged_count(&self) -> Option<u64> {
+        None
+    }
+
+    pub fn write_merged_count(&self) -> Option<u64> {
+        None
+    }
 }
 
 fn filter_map_block_devices(device: iokit::IoObject) -> Result<Option<IoCounters>> {
@@ -7,7 +7,7 @@ use heim_common::prelude::*;
 use super::bindings;
 use crate::FileSystem;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Partition {
     device: String,
     fs: FileSystem,
@@ -31,6 +31,14 @@ impl Partition {
     pub fn raw_flags(&self) -> u32 {
         self.flags
     }
+
+    pub fn is_same_mount(&self, other: &Partition) -> bool {
+        self.device == other.device && self.mount_point == other.mount_point && self.fs == other.fs
+    }
+
+    pub fn is_remounted(&self, other: &Partition) -> bool {
+        self.flags != other.flags
+    }
 }
 
 // TODO: Since `from` may fail in fact, replace it with a `try_from`
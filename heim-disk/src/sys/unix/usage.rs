@@ -6,6 +6,7 @@ use std::path::Path;
 
 use heim_common::prelude::*;
 use heim_common::units::{information, ratio, Information, Ratio};
+use heim_runtime as rt;
 
 use crate::os::unix::Flags;
 
@@ -47,6 +48,48 @@ impl Usage {
     pub fn flags(&self) -> Flags {
         Flags::from_bits_truncate(self.0.f_flag)
     }
+
+    pub fn inodes_total(&self) -> u64 {
+        u64::from(self.0.f_files)
+    }
+
+    pub fn inodes_free(&self) -> u64 {
+        u64::from(self.0.f_ffree)
+    }
+
+    pub fn inodes_available(&self) -> u64 {
+        u64::from(self.0.f_favail)
+    }
+
+    pub fn inodes_ratio(&self) -> Option<Ratio> {
+        // Some filesystems (ex. btrfs or FAT) do not have fixed inode tables
+        // and report zeroes here
+        let used = self.inodes_total().saturating_sub(self.inodes_free());
+        let total_user = used + self.inodes_available();
+        if total_user == 0 {
+            return None;
+        }
+
+        Some(Ratio::new::<ratio::ratio>(
+            (used as f64 / total_user as f64) as f32,
+        ))
+    }
+
+    pub fn block_size(&self) -> Information {
+        Information::new::<information::byte>(u64::from(self.0.f_bsize))
+    }
+
+    pub fn fragment_size(&self) -> Information {
+        Information::new::<information::byte>(u64::from(self.0.f_frsize))
+    }
+
+    pub fn filesystem_id(&self) -> u64 {
+        u64::from(self.0.f_fsid)
+    }
+
+    pub fn max_filename_length(&self) -> u64 {
+        u64::from(self.0.f_namemax)
+    }
 }
 
 // TODO: Stub
@@ -67,13 +110,17 @@ pub async fn usage<T: AsRef<Path>>(path: T) -> Result<Usage> {
         })
         .map_err(|e| Error::from(e).with_message("Invalid path"))?;
 
-    let mut vfs = mem::MaybeUninit::<libc::statvfs>::uninit();
-    let result = unsafe { libc::statvfs(path.as_ptr(), vfs.as_mut_ptr()) };
-
-    if result == 0 {
-        let vfs = unsafe { vfs.assume_init() };
-        Ok(Usage(vfs))
-    } else {
-        Err(Error::last_os_error().with_ffi("statvfs"))
-    }
+    // `statvfs` might block for a long time on unresponsive network filesystems
+    rt::spawn_blocking(move || {
+        let mut vfs = mem::MaybeUninit::<libc::statvfs>::uninit();
+        let result = unsafe { libc::statvfs(path.as_ptr(), vfs.as_mut_ptr()) };
+
+        if result == 0 {
+            let vfs = unsafe { vfs.assume_init() };
+            Ok(Usage(vfs))
+        } else {
+            Err(Error::last_os_error().with_ffi("statvfs"))
+        }
+    })
+    .await
 }
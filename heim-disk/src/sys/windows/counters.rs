@@ -45,6 +45,18 @@ impl IoCounters {
     pub fn write_time(&self) -> Time {
         self.write_time
     }
+
+    pub fn busy_time(&self) -> Option<Time> {
+        None
+    }
+
+    pub fn read_merged_count(&self) -> Option<u64> {
+        None
+    }
+
+    pub fn write_merged_count(&self) -> Option<u64> {
+        None
+    }
 }
 
 fn inner_stream<F>(mut filter: F) -> impl Iterator<Item = Result<IoCounters>>
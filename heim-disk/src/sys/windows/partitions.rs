@@ -7,7 +7,7 @@ use super::bindings;
 use crate::os::windows::{DriveType, Flags};
 use crate::FileSystem;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Partition {
     // Might be missing for a remote FS, such as SMB
     volume: Option<OsString>,
@@ -38,6 +38,16 @@ impl Partition {
     pub fn drive_type(&self) -> Option<DriveType> {
         self.drive_type
     }
+
+    pub fn is_same_mount(&self, other: &Partition) -> bool {
+        self.volume == other.volume
+            && self.mount_point == other.mount_point
+            && self.file_system == other.file_system
+    }
+
+    pub fn is_remounted(&self, other: &Partition) -> bool {
+        self.flags != other.flags || self.drive_type != other.drive_type
+    }
 }
 
 pub async fn partitions() -> Result<impl Stream<Item = Result<Partition>>> {
@@ -4,7 +4,10 @@ use std::path::Path;
 use heim_common::prelude::*;
 use heim_common::units::{Information, Ratio};
 
-use crate::sys;
+use crate::{partitions, sys, Partition};
+
+// Number of `statvfs` calls running at once
+const USAGES_CONCURRENCY: usize = 8;
 
 /// Disk usage statistics.
 ///
@@ -59,3 +62,24 @@ where
 {
     sys::usage(path).await.map(Into::into)
 }
+
+/// Returns a stream over all mounted [Partitions] paired with their [Usage] statistics.
+///
+/// Usage statistics are fetched concurrently, while the stream keeps the partitions order.
+/// Failure to fetch usage for one partition (ex. stale NFS file handle or permission error)
+/// does not stop the stream, the error is returned along with that partition instead.
+///
+/// [Partitions]: ./struct.Partition.html
+/// [Usage]: ./struct.Usage.html
+pub async fn usages() -> Result<impl Stream<Item = Result<(Partition, Result<Usage>)>>> {
+    let stream = partitions().await?.map_ok(|partition| async move {
+        let usage = partition
+            .usage()
+            .await
+            .map_err(|e| e.with_file(partition.mount_point()));
+
+        Ok((partition, usage))
+    });
+
+    Ok(stream.try_buffered(USAGES_CONCURRENCY))
+}
@@ -78,6 +78,30 @@ async fn smoke_usage() -> Result<(), Box<dyn Error>> {
         use heim_disk::os::unix::UsageExt;
 
         let _ = usage.flags();
+        let _ = usage.inodes_total();
+        let _ = usage.inodes_free();
+        let _ = usage.inodes_available();
+        let _ = usage.inodes_ratio();
+        let _ = usage.block_size();
+        let _ = usage.fragment_size();
+        let _ = usage.filesystem_id();
+        let _ = usage.max_filename_length();
+    }
+
+    Ok(())
+}
+
+#[heim_derive::test]
+async fn smoke_usages() -> Result<(), Box<dyn Error>> {
+    let usages = disk::usages().await?;
+    futures::pin_mut!(usages);
+    while let Some(item) = usages.next().await {
+        let (partition, usage) = item?;
+
+        let _ = partition.mount_point();
+        if let Ok(usage) = usage {
+            let _ = usage.total();
+        }
     }
 
     Ok(())
@@ -102,6 +126,27 @@ async fn smoke_io_counters() -> Result<(), Box<dyn Error>> {
         let _ = count.write_count();
         let _ = count.read_bytes();
         let _ = count.write_bytes();
+        let _ = count.busy_time();
+        let _ = count.read_merged_count();
+        let _ = count.write_merged_count();
+
+        #[cfg(target_os = "linux")]
+        {
+            use heim_disk::os::linux::IoCountersExt;
+
+            let _ = count.read_time();
+            let _ = count.write_time();
+            let _ = count.io_in_progress();
+            let _ = count.weighted_io_time();
+            let _ = count.discard_count();
+            let _ = count.discard_bytes();
+            let _ = count.flush_count();
+            if let Some(dm) = count.dm_device() {
+                let _ = dm.name();
+                let _ = dm.kind();
+                let _ = dm.lvm_names();
+            }
+        }
     }
 
     Ok(())
@@ -130,3 +175,228 @@ async fn smoke_io_counters_physical() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[heim_derive::test]
+#[cfg(target_os = "linux")]
+async fn smoke_iostat() -> Result<(), Box<dyn Error>> {
+    use std::time::Duration;
+
+    use futures::TryStreamExt;
+    use heim_disk::os::linux::iostat;
+
+    let before = disk::io_counters().await?.try_collect::<Vec<_>>().await?;
+    let after = disk::io_counters().await?.try_collect::<Vec<_>>().await?;
+
+    for stat in iostat(before, after, Duration::from_millis(10)) {
+        let _ = stat.device_name();
+        let _ = stat.reads_per_second();
+        let _ = stat.writes_per_second();
+        let _ = stat.read_rate();
+        let _ = stat.write_rate();
+        let _ = stat.read_await();
+        let _ = stat.write_await();
+        let _ = stat.average_queue_size();
+        let _ = stat.utilization();
+        let _ = stat.discards_per_second();
+        let _ = stat.flush_await();
+    }
+
+    Ok(())
+}
+
+#[heim_derive::test]
+#[cfg(target_os = "linux")]
+async fn smoke_block_devices() -> Result<(), Box<dyn Error>> {
+    use heim_common::units::information;
+    use heim_disk::os::linux::block_devices;
+
+    let devices = block_devices().await?;
+    futures::pin_mut!(devices);
+    while let Some(device) = devices.next().await {
+        let device = device?;
+
+        let _ = device.name();
+        let _ = device.size();
+        let _ = device.logical_block_size();
+        let _ = device.physical_block_size();
+        let _ = device.is_rotational();
+        let _ = device.model();
+        let _ = device.serial();
+        let _ = device.wwn();
+        let _ = device.scheduler();
+        let _ = device.queue_depth();
+        let _ = device.backing_disks();
+
+        // `/proc/diskstats` sectors are 512 bytes long, real sectors can't be smaller
+        if let Some(sector_size) = device.hw_sector_size() {
+            assert_eq!(sector_size.get::<information::byte>() % 512, 0);
+        }
+
+        for partition in device.partitions() {
+            let _ = partition.name();
+            let _ = partition.number();
+            let _ = partition.size();
+            let _ = partition.holders();
+        }
+    }
+
+    Ok(())
+}
+
+#[heim_derive::test]
+#[cfg(target_os = "linux")]
+async fn smoke_mount_table() -> Result<(), Box<dyn Error>> {
+    use heim_disk::os::linux::{mount_table, PartitionExt};
+
+    let table = mount_table().await?;
+    for mount in table.mounts() {
+        let _ = mount.mount_id();
+        let _ = mount.root();
+        let _ = mount.shared();
+        let _ = mount.source();
+        let _ = table.parent(mount);
+        let _ = table.bind_source_path(mount);
+    }
+
+    let partitions = disk::partitions().await?;
+    futures::pin_mut!(partitions);
+    while let Some(partition) = partitions.next().await {
+        let partition = partition?;
+
+        assert_eq!(
+            partition.mount_info().mount_point(),
+            partition.mount_point()
+        );
+    }
+
+    Ok(())
+}
+
+#[heim_derive::test]
+async fn smoke_mount_for_path() -> Result<(), Box<dyn Error>> {
+    let partition = disk::mount_for_path(std::env::current_dir()?).await?;
+    let _ = partition.mount_point();
+
+    #[cfg(target_os = "linux")]
+    {
+        use heim_disk::os::linux::backing_device;
+
+        let device = backing_device(&partition).await?;
+        let _ = device.major();
+        let _ = device.name();
+        let _ = device.disks();
+    }
+
+    Ok(())
+}
+
+#[heim_derive::test]
+#[cfg(target_os = "linux")]
+async fn smoke_md_arrays() -> Result<(), Box<dyn Error>> {
+    use heim_disk::os::linux::md_arrays;
+
+    let arrays = md_arrays().await?;
+    futures::pin_mut!(arrays);
+    while let Some(array) = arrays.next().await {
+        let array = array?;
+
+        let _ = array.name();
+        let _ = array.level();
+        let _ = array.is_degraded();
+        for member in array.members() {
+            let _ = member.slot();
+            let _ = member.is_faulty();
+        }
+        if let Some(sync) = array.sync() {
+            let _ = sync.progress();
+        }
+    }
+
+    Ok(())
+}
+
+#[heim_derive::test]
+#[cfg(target_os = "linux")]
+async fn smoke_fs_identities() -> Result<(), Box<dyn Error>> {
+    use heim_disk::os::linux::{dm_device, fs_identities, fs_identity, fs_identity_by_uuid};
+
+    let identities = fs_identities().await?;
+    futures::pin_mut!(identities);
+    while let Some(identity) = identities.next().await {
+        let identity = identity?;
+
+        let _ = identity.device_name();
+        let _ = identity.label();
+        let _ = identity.part_uuid();
+        let _ = identity.part_label();
+        if let Some(uuid) = identity.uuid() {
+            let found = fs_identity_by_uuid(uuid).await?;
+            assert!(found.is_some());
+        }
+    }
+
+    let partitions = disk::partitions().await?;
+    futures::pin_mut!(partitions);
+    while let Some(partition) = partitions.next().await {
+        let partition = partition?;
+
+        let _ = fs_identity(&partition).await?;
+        let _ = dm_device(&partition).await?;
+    }
+
+    Ok(())
+}
+
+#[heim_derive::test]
+async fn smoke_watch_partitions() -> Result<(), Box<dyn Error>> {
+    // Waiting for the events would block the test forever
+    let events = disk::watch_partitions().await?;
+    drop(events);
+
+    Ok(())
+}
+
+#[heim_derive::test]
+#[cfg(target_os = "linux")]
+async fn smoke_btrfs_filesystems() -> Result<(), Box<dyn Error>> {
+    use heim_disk::os::linux::{btrfs_filesystem, btrfs_filesystems};
+
+    let filesystems = btrfs_filesystems().await?;
+    futures::pin_mut!(filesystems);
+    while let Some(filesystem) = filesystems.next().await {
+        let filesystem = filesystem?;
+
+        let _ = filesystem.uuid();
+        let _ = filesystem.label();
+        let _ = filesystem.devices();
+        for info in filesystem.device_infos() {
+            let _ = info.errors();
+        }
+        if let Some(data) = filesystem.data() {
+            let _ = data.profiles();
+        }
+    }
+
+    let partitions = disk::partitions().await?;
+    futures::pin_mut!(partitions);
+    while let Some(partition) = partitions.next().await {
+        let _ = btrfs_filesystem(&partition?).await?;
+    }
+
+    Ok(())
+}
+
+#[heim_derive::test]
+async fn smoke_partitions_filtered() -> Result<(), Box<dyn Error>> {
+    let partitions = disk::partitions_filtered(|fs: &disk::FileSystem| !fs.is_pseudo()).await?;
+    futures::pin_mut!(partitions);
+    while let Some(part) = partitions.next().await {
+        let part = part?;
+
+        assert!(!part.file_system().is_pseudo());
+        let _ = part.file_system().is_network();
+        let _ = part.file_system().is_read_only_image();
+    }
+
+    Ok(())
+}
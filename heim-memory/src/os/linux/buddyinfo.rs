@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+use heim_common::prelude::*;
+use heim_common::units::{ratio, Ratio};
+
+#[cfg(target_os = "linux")]
+use crate::sys;
+
+/// Memory zone watermarks.
+///
+/// Values are read from the `/proc/zoneinfo` file and expressed in pages.
+#[derive(Debug, Clone, Default)]
+pub struct Watermarks {
+    pub(crate) free: u64,
+    pub(crate) min: u64,
+    pub(crate) low: u64,
+    pub(crate) high: u64,
+    pub(crate) managed: Option<u64>,
+}
+
+impl Watermarks {
+    /// Returns number of free pages in the zone.
+    pub fn free(&self) -> u64 {
+        self.free
+    }
+
+    /// Returns minimal number of free pages; below it only atomic
+    /// and emergency allocations are served and direct reclaim happens.
+    pub fn min(&self) -> u64 {
+        self.min
+    }
+
+    /// Returns number of free pages below which `kswapd` is woken up.
+    pub fn low(&self) -> u64 {
+        self.low
+    }
+
+    /// Returns number of free pages at which `kswapd` goes back to sleep.
+    pub fn high(&self) -> u64 {
+        self.high
+    }
+
+    /// Returns number of pages managed by the buddy allocator in the zone.
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 3.8, older versions always return `None`.
+    pub fn managed(&self) -> Option<u64> {
+        self.managed
+    }
+}
+
+/// Free memory blocks of one memory zone, grouped by the allocation order.
+///
+/// Block of order `N` consists of `2^N` contiguous pages.
+/// Values are read from the `/proc/buddyinfo` file.
+#[derive(Debug, Clone)]
+pub struct BuddyInfo {
+    pub(crate) node: u32,
+    pub(crate) zone: String,
+    pub(crate) free_blocks: Vec<u64>,
+    pub(crate) free_blocks_by_type: Option<HashMap<String, Vec<u64>>>,
+    pub(crate) watermarks: Option<Watermarks>,
+}
+
+impl BuddyInfo {
+    /// Returns NUMA node number this zone belongs to.
+    pub fn node(&self) -> u32 {
+        self.node
+    }
+
+    /// Returns memory zone name, ex. `DMA32` or `Normal`.
+    pub fn zone(&self) -> &str {
+        &self.zone
+    }
+
+    /// Returns numbers of free blocks, indexed by the allocation order.
+    pub fn free_blocks(&self) -> &[u64] {
+        &self.free_blocks
+    }
+
+    /// Returns numbers of free blocks for each migrate type (ex. `Movable` or `Unmovable`),
+    /// indexed by the allocation order.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if `/proc/pagetypeinfo` file is not readable,
+    /// since Linux 5.5 it is accessible by root only.
+    pub fn free_blocks_by_type(&self) -> Option<&HashMap<String, Vec<u64>>> {
+        self.free_blocks_by_type.as_ref()
+    }
+
+    /// Returns zone watermarks.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if zone is missing in the `/proc/zoneinfo` file.
+    pub fn watermarks(&self) -> Option<&Watermarks> {
+        self.watermarks.as_ref()
+    }
+
+    /// Returns total number of free pages in the zone.
+    pub fn free_pages(&self) -> u64 {
+        self.free_pages_at_order(0)
+    }
+
+    /// Returns number of free pages which are part of blocks of the `order` or higher.
+    pub fn free_pages_at_order(&self, order: usize) -> u64 {
+        self.free_blocks
+            .iter()
+            .enumerate()
+            .skip(order)
+            .map(|(order, count)| count << order)
+            .sum()
+    }
+
+    /// Returns fragmentation index for the allocation `order`:
+    /// the share of free memory which is available as blocks of the `order` or higher.
+    ///
+    /// Value of `1.0` means that all free memory can be used
+    /// for allocations of this size, and values near `0.0` mean
+    /// that such allocations will require memory compaction.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if there are no free pages in the zone.
+    pub fn fragmentation_index(&self, order: usize) -> Option<Ratio> {
+        let total = self.free_pages();
+        if total > 0 {
+            let available = self.free_pages_at_order(order);
+
+            Some(Ratio::new::<ratio::ratio>(available as f32 / total as f32))
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns a stream over [free memory blocks] for each memory zone.
+///
+/// Stream is ordered in the same way as the `/proc/buddyinfo` file,
+/// by NUMA node and then by memory zone.
+///
+/// [free memory blocks]: ./struct.BuddyInfo.html
+pub async fn buddyinfo() -> Result<impl Stream<Item = Result<BuddyInfo>>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::buddyinfo().await
+        } else {
+            Ok(stream::iter(vec![]))
+        }
+    }
+}
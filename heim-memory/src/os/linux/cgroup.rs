@@ -0,0 +1,65 @@
+use heim_common::prelude::*;
+use heim_common::units::Information;
+
+#[cfg(target_os = "linux")]
+use crate::sys;
+
+pub use heim_common::sys::linux::cgroup::LimitSource;
+
+/// Memory limit and usage as seen from inside of the container.
+///
+/// Control group limits are checked for the current process group
+/// and all its parent groups, the lowest one wins.
+/// Limit is never greater than the host physical memory.
+#[derive(Debug, Clone)]
+pub struct ContainerMemory {
+    pub(crate) limit: Information,
+    pub(crate) limit_source: LimitSource,
+    pub(crate) usage: Information,
+    pub(crate) usage_source: LimitSource,
+}
+
+impl ContainerMemory {
+    /// Returns effective memory limit.
+    pub fn limit(&self) -> Information {
+        self.limit
+    }
+
+    /// Returns where the [limit](#method.limit) value came from.
+    pub fn limit_source(&self) -> &LimitSource {
+        &self.limit_source
+    }
+
+    /// Returns memory used by the control group processes, including page cache.
+    ///
+    /// If process is not constrained by the control group,
+    /// host memory usage (total minus available) is returned.
+    pub fn usage(&self) -> Information {
+        self.usage
+    }
+
+    /// Returns where the [usage](#method.usage) value came from.
+    pub fn usage_source(&self) -> &LimitSource {
+        &self.usage_source
+    }
+}
+
+/// Returns [memory limit and usage] for the control group of the current process.
+///
+/// Both cgroup v1 (`memory.limit_in_bytes`, `memory.usage_in_bytes`)
+/// and cgroup v2 (`memory.max`, `memory.current`) hierarchies are supported.
+///
+/// Unlike the [memory](../../fn.memory.html) function,
+/// which always reports host memory, this one should be used
+/// by applications running inside of containers.
+///
+/// [memory limit and usage]: ./struct.ContainerMemory.html
+pub async fn container_memory() -> Result<ContainerMemory> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::container_memory().await
+        } else {
+            unimplemented!("For documentation rendering")
+        }
+    }
+}
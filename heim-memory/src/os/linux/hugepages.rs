@@ -0,0 +1,186 @@
+use heim_common::prelude::*;
+use heim_common::units::Information;
+
+#[cfg(target_os = "linux")]
+use crate::sys;
+
+/// Huge pages pool of one particular page size.
+///
+/// Values are read from the `/sys/kernel/mm/hugepages/hugepages-*kB/` directories
+/// for system-wide pools and from the `/sys/devices/system/node/node*/hugepages/hugepages-*kB/`
+/// directories for per-NUMA node pools.
+#[derive(Debug, Clone)]
+pub struct HugePages {
+    pub(crate) page_size: Information,
+    pub(crate) total: u64,
+    pub(crate) free: u64,
+    pub(crate) reserved: Option<u64>,
+    pub(crate) surplus: u64,
+}
+
+impl HugePages {
+    /// Returns huge page size of this pool.
+    pub fn page_size(&self) -> Information {
+        self.page_size
+    }
+
+    /// Returns number of huge pages in the pool (`nr_hugepages`).
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Returns number of huge pages in the pool that are not yet allocated (`free_hugepages`).
+    pub fn free(&self) -> u64 {
+        self.free
+    }
+
+    /// Returns number of allocated huge pages.
+    pub fn used(&self) -> u64 {
+        self.total.saturating_sub(self.free)
+    }
+
+    /// Returns number of huge pages for which a commitment to allocate from the pool
+    /// has been made, but no allocation has yet been made (`resv_hugepages`).
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` for per-NUMA node pools, as kernel does not track it per node.
+    pub fn reserved(&self) -> Option<u64> {
+        self.reserved
+    }
+
+    /// Returns number of huge pages allocated above the `nr_hugepages` value
+    /// (`surplus_hugepages`).
+    pub fn surplus(&self) -> u64 {
+        self.surplus
+    }
+}
+
+/// Transparent huge pages usage mode (`/sys/kernel/mm/transparent_hugepage/enabled`).
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ThpEnabled {
+    /// Transparent huge pages are used for all memory regions.
+    Always,
+    /// Transparent huge pages are used only for regions marked with `madvise(MADV_HUGEPAGE)`.
+    Madvise,
+    /// Transparent huge pages are disabled.
+    Never,
+}
+
+/// Transparent huge pages defragmentation mode (`/sys/kernel/mm/transparent_hugepage/defrag`).
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ThpDefrag {
+    /// Allocations stall for direct reclaim and compaction.
+    Always,
+    /// Allocations wake up `kswapd` and `kcompactd` and fall back to regular pages.
+    Defer,
+    /// Same as `Defer`, but regions marked with `madvise(MADV_HUGEPAGE)`
+    /// are stalling for direct reclaim.
+    DeferMadvise,
+    /// Only regions marked with `madvise(MADV_HUGEPAGE)` are stalling for direct reclaim.
+    Madvise,
+    /// Allocations never stall for direct reclaim and compaction.
+    Never,
+}
+
+/// Transparent huge pages settings and usage.
+#[derive(Debug, Clone)]
+pub struct TransparentHugePages {
+    pub(crate) enabled: Option<ThpEnabled>,
+    pub(crate) defrag: Option<ThpDefrag>,
+    pub(crate) page_size: Option<Information>,
+    pub(crate) anon: Option<Information>,
+    pub(crate) shmem: Option<Information>,
+    pub(crate) file: Option<Information>,
+}
+
+impl TransparentHugePages {
+    /// Returns selected transparent huge pages usage mode.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if kernel was built without transparent huge pages support
+    /// or if selected mode is unknown.
+    pub fn enabled(&self) -> Option<ThpEnabled> {
+        self.enabled
+    }
+
+    /// Returns selected transparent huge pages defragmentation mode.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if kernel was built without transparent huge pages support
+    /// or if selected mode is unknown.
+    pub fn defrag(&self) -> Option<ThpDefrag> {
+        self.defrag
+    }
+
+    /// Returns transparent huge page size (`hpage_pmd_size`).
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.10, older versions always return `None`.
+    pub fn page_size(&self) -> Option<Information> {
+        self.page_size
+    }
+
+    /// Returns the amount of anonymous memory backed by transparent huge pages
+    /// (`AnonHugePages` key in the `/proc/meminfo`).
+    pub fn anon(&self) -> Option<Information> {
+        self.anon
+    }
+
+    /// Returns the amount of shared memory and `tmpfs` backed by huge pages
+    /// (`ShmemHugePages` key in the `/proc/meminfo`).
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.8, older versions always return `None`.
+    pub fn shmem(&self) -> Option<Information> {
+        self.shmem
+    }
+
+    /// Returns the amount of page cache backed by huge pages
+    /// (`FileHugePages` key in the `/proc/meminfo`).
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 5.4, older versions always return `None`.
+    pub fn file(&self) -> Option<Information> {
+        self.file
+    }
+}
+
+/// Returns a stream over system-wide [huge pages pools], one for each supported page size.
+///
+/// Stream is ordered by the page size.
+///
+/// ## Compatibility
+///
+/// If kernel was built without huge pages support, stream will be empty.
+///
+/// [huge pages pools]: ./struct.HugePages.html
+pub async fn huge_pages() -> Result<impl Stream<Item = Result<HugePages>>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::huge_pages().await
+        } else {
+            Ok(stream::iter(vec![]))
+        }
+    }
+}
+
+/// Returns [transparent huge pages] settings and usage.
+///
+/// [transparent huge pages]: ./struct.TransparentHugePages.html
+pub async fn transparent_huge_pages() -> Result<TransparentHugePages> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::transparent_huge_pages().await
+        } else {
+            unimplemented!("For documentation rendering")
+        }
+    }
+}
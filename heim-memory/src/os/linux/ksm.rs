@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use heim_common::prelude::*;
+use heim_common::units::{ratio, Ratio};
+
+#[cfg(target_os = "linux")]
+use crate::sys;
+
+/// Kernel samepage merging (KSM) statistics.
+///
+/// Values are read from the `/sys/kernel/mm/ksm/` directory,
+/// page counters are expressed in pages.
+#[derive(Debug, Clone)]
+pub struct Ksm {
+    pub(crate) values: HashMap<String, u64>,
+}
+
+impl Ksm {
+    /// Returns raw numeric value by the file name, ex. `sleep_millisecs`.
+    pub fn get(&self, name: &str) -> Option<u64> {
+        self.values.get(name).copied()
+    }
+
+    /// Returns `true` if KSM daemon is merging pages (`run` file equals `1`).
+    pub fn is_running(&self) -> bool {
+        self.get("run") == Some(1)
+    }
+
+    /// Returns number of shared pages in use (`pages_shared`).
+    pub fn pages_shared(&self) -> u64 {
+        self.get("pages_shared").unwrap_or(0)
+    }
+
+    /// Returns number of pages sharing the shared pages,
+    /// roughly the amount of memory saved (`pages_sharing`).
+    pub fn pages_sharing(&self) -> u64 {
+        self.get("pages_sharing").unwrap_or(0)
+    }
+
+    /// Returns number of pages which are unique,
+    /// but repeatedly checked for merging (`pages_unshared`).
+    pub fn pages_unshared(&self) -> u64 {
+        self.get("pages_unshared").unwrap_or(0)
+    }
+
+    /// Returns number of pages which are changing too fast to be merged (`pages_volatile`).
+    pub fn pages_volatile(&self) -> u64 {
+        self.get("pages_volatile").unwrap_or(0)
+    }
+
+    /// Returns number of times all mergeable areas have been scanned (`full_scans`).
+    pub fn full_scans(&self) -> u64 {
+        self.get("full_scans").unwrap_or(0)
+    }
+
+    /// Returns how many pages are sharing each shared page on average.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if there are no shared pages.
+    pub fn sharing_ratio(&self) -> Option<Ratio> {
+        match self.pages_shared() {
+            0 => None,
+            shared => Some(Ratio::new::<ratio::ratio>(
+                self.pages_sharing() as f32 / shared as f32,
+            )),
+        }
+    }
+}
+
+/// Returns [KSM] statistics.
+///
+/// ## Returns
+///
+/// Returns `Ok(None)` if kernel was built without KSM support.
+///
+/// [KSM]: ./struct.Ksm.html
+pub async fn ksm() -> Result<Option<Ksm>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::ksm().await
+        } else {
+            unimplemented!("For documentation rendering")
+        }
+    }
+}
@@ -0,0 +1,424 @@
+// Reference: https://gitlab.com/procps-ng/procps/blob/master/proc/sysinfo.c
+use heim_common::units::{information, Information};
+
+use crate::Memory;
+
+/// Linux-specific extension to [`Memory`]
+///
+/// Values are read from the `/proc/meminfo` file;
+/// methods returning `Option` will return `None` if the key
+/// is not available in the running kernel.
+pub trait MemoryExt {
+    /// The amount of physical RAM used.
+    ///
+    /// It is designed for informational purposes only and might vary vastly
+    /// from platform to platform.
+    fn used(&self) -> Information;
+
+    /// The amount of physical RAM used for file buffers.
+    fn buffers(&self) -> Information;
+
+    /// The amount of physical RAM used as cache memory.
+    fn cached(&self) -> Information;
+
+    /// The amount of memory that may be simultaneously accessed by multiple processes.
+    fn shared(&self) -> Information;
+
+    /// The total amount of buffer or page cache memory, that is in active use.
+    ///
+    /// This is memory that has been recently used and is usually not reclaimed for other purposes.
+    fn active(&self) -> Information;
+
+    ///  The total amount of buffer or page cache memory, that are free and available.
+    ///
+    /// This is memory that has not been recently used and can be reclaimed for other purposes.
+    fn inactive(&self) -> Information;
+
+    /// Memory that once was swapped out, is swapped back in
+    /// but still also is in the swap file.
+    ///
+    /// Represents `SwapCached` key.
+    fn swap_cached(&self) -> Option<Information>;
+
+    /// Anonymous memory that has been used more recently
+    /// and usually not swapped out.
+    ///
+    /// Represents `Active(anon)` key.
+    fn active_anon(&self) -> Option<Information>;
+
+    /// Anonymous memory that has not been used recently
+    /// and can be swapped out.
+    ///
+    /// Represents `Inactive(anon)` key.
+    fn inactive_anon(&self) -> Option<Information>;
+
+    /// Page cache memory that has been used more recently
+    /// and usually not reclaimed until needed.
+    ///
+    /// Represents `Active(file)` key.
+    fn active_file(&self) -> Option<Information>;
+
+    /// Page cache memory that can be reclaimed without huge performance impact.
+    ///
+    /// Represents `Inactive(file)` key.
+    fn inactive_file(&self) -> Option<Information>;
+
+    /// Memory that cannot be paged out, such as `mlock`-ed pages or `ramfs` backed pages.
+    ///
+    /// Represents `Unevictable` key.
+    fn unevictable(&self) -> Option<Information>;
+
+    /// Memory locked with `mlock(2)`.
+    ///
+    /// Represents `Mlocked` key.
+    fn mlocked(&self) -> Option<Information>;
+
+    /// Memory which is waiting to get written back to the disk.
+    ///
+    /// Represents `Dirty` key.
+    fn dirty(&self) -> Option<Information>;
+
+    /// Memory which is actively being written back to the disk.
+    ///
+    /// Represents `Writeback` key.
+    fn writeback(&self) -> Option<Information>;
+
+    /// Non-file backed pages mapped into userspace page tables.
+    ///
+    /// Represents `AnonPages` key.
+    fn anon_pages(&self) -> Option<Information>;
+
+    /// Files which have been mapped into memory, such as libraries.
+    ///
+    /// Represents `Mapped` key.
+    fn mapped(&self) -> Option<Information>;
+
+    /// Kernel allocations that the kernel will attempt to reclaim under memory pressure.
+    ///
+    /// Represents `KReclaimable` key.
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.20.
+    fn kernel_reclaimable(&self) -> Option<Information>;
+
+    /// In-kernel data structures cache.
+    ///
+    /// Represents `Slab` key.
+    fn slab(&self) -> Option<Information>;
+
+    /// Part of [slab](#tymethod.slab) that might be reclaimed, such as caches.
+    ///
+    /// Represents `SReclaimable` key.
+    fn slab_reclaimable(&self) -> Option<Information>;
+
+    /// Part of [slab](#tymethod.slab) that cannot be reclaimed under memory pressure.
+    ///
+    /// Represents `SUnreclaim` key.
+    fn slab_unreclaimable(&self) -> Option<Information>;
+
+    /// Memory used by the kernel stacks of all tasks.
+    ///
+    /// Represents `KernelStack` key.
+    fn kernel_stack(&self) -> Option<Information>;
+
+    /// Memory dedicated to the lowest level of page tables.
+    ///
+    /// Represents `PageTables` key.
+    fn page_tables(&self) -> Option<Information>;
+
+    /// NFS pages sent to the server, but not yet committed to stable storage.
+    ///
+    /// Represents `NFS_Unstable` key.
+    ///
+    /// ## Compatibility
+    ///
+    /// Always zero since Linux 4.15.
+    fn nfs_unstable(&self) -> Option<Information>;
+
+    /// Memory used for block device "bounce buffers".
+    ///
+    /// Represents `Bounce` key.
+    fn bounce(&self) -> Option<Information>;
+
+    /// Memory used by FUSE for temporary writeback buffers.
+    ///
+    /// Represents `WritebackTmp` key.
+    fn writeback_tmp(&self) -> Option<Information>;
+
+    /// Total amount of memory currently available to be allocated on the system,
+    /// based on the overcommit ratio (`vm.overcommit_ratio`).
+    ///
+    /// Represents `CommitLimit` key.
+    fn commit_limit(&self) -> Option<Information>;
+
+    /// The amount of memory presently allocated on the system,
+    /// including memory which was not used yet by processes.
+    ///
+    /// Represents `Committed_AS` key.
+    fn committed_as(&self) -> Option<Information>;
+
+    /// Total size of vmalloc virtual address space.
+    ///
+    /// Represents `VmallocTotal` key.
+    fn vmalloc_total(&self) -> Option<Information>;
+
+    /// Amount of vmalloc area which is used.
+    ///
+    /// Represents `VmallocUsed` key.
+    fn vmalloc_used(&self) -> Option<Information>;
+
+    /// Memory allocated to the percpu allocator used to back percpu allocations.
+    ///
+    /// Represents `Percpu` key.
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.16.
+    fn percpu(&self) -> Option<Information>;
+
+    /// Memory the kernel identified as corrupted.
+    ///
+    /// Represents `HardwareCorrupted` key.
+    fn hardware_corrupted(&self) -> Option<Information>;
+
+    /// Non-file backed huge pages mapped into userspace page tables.
+    ///
+    /// Represents `AnonHugePages` key.
+    fn anon_huge_pages(&self) -> Option<Information>;
+
+    /// Memory used by shared memory and `tmpfs` allocated with huge pages.
+    ///
+    /// Represents `ShmemHugePages` key.
+    fn shmem_huge_pages(&self) -> Option<Information>;
+
+    /// Memory used for filesystem data (page cache) allocated with huge pages.
+    ///
+    /// Represents `FileHugePages` key.
+    fn file_huge_pages(&self) -> Option<Information>;
+
+    /// Size of the huge pages pool in pages.
+    ///
+    /// Represents `HugePages_Total` key.
+    fn huge_pages_total(&self) -> Option<u64>;
+
+    /// Number of huge pages in the pool that are not yet allocated.
+    ///
+    /// Represents `HugePages_Free` key.
+    fn huge_pages_free(&self) -> Option<u64>;
+
+    /// Number of huge pages for which a commitment to allocate from the pool
+    /// has been made, but no allocation has yet been made.
+    ///
+    /// Represents `HugePages_Rsvd` key.
+    fn huge_pages_reserved(&self) -> Option<u64>;
+
+    /// Number of huge pages in the pool above the `vm.nr_hugepages` value.
+    ///
+    /// Represents `HugePages_Surp` key.
+    fn huge_pages_surplus(&self) -> Option<u64>;
+
+    /// Default huge page size.
+    ///
+    /// Represents `Hugepagesize` key.
+    fn huge_page_size(&self) -> Option<Information>;
+
+    /// Total amount of memory consumed by huge pages of all sizes.
+    ///
+    /// Represents `Hugetlb` key.
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.16.
+    fn hugetlb(&self) -> Option<Information>;
+
+    /// Returns value for any `/proc/meminfo` key expressed in memory units,
+    /// including kernel-specific keys not covered by other methods.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if key is missing or if it is an unitless counter,
+    /// such as `HugePages_Total`.
+    fn get(&self, key: &str) -> Option<Information>;
+}
+
+#[cfg(target_os = "linux")]
+impl MemoryExt for Memory {
+    fn used(&self) -> Information {
+        let inner = self.as_ref();
+
+        let mut used = inner.total() - inner.free() - self.cached() - self.buffers();
+        if used <= Information::new::<information::byte>(0) {
+            // May be symptomatic of running within a LCX container where such
+            // values will be dramatically distorted over those of the host.
+            // Source: psutil
+            used = inner.total() - inner.free()
+        }
+
+        used
+    }
+
+    fn buffers(&self) -> Information {
+        self.as_ref().buffers()
+    }
+
+    fn cached(&self) -> Information {
+        self.as_ref().cached()
+    }
+
+    fn shared(&self) -> Information {
+        self.as_ref().shared()
+    }
+
+    fn active(&self) -> Information {
+        self.as_ref().active()
+    }
+
+    fn inactive(&self) -> Information {
+        self.as_ref().inactive()
+    }
+
+    fn swap_cached(&self) -> Option<Information> {
+        self.as_ref().size("SwapCached")
+    }
+
+    fn active_anon(&self) -> Option<Information> {
+        self.as_ref().size("Active(anon)")
+    }
+
+    fn inactive_anon(&self) -> Option<Information> {
+        self.as_ref().size("Inactive(anon)")
+    }
+
+    fn active_file(&self) -> Option<Information> {
+        self.as_ref().size("Active(file)")
+    }
+
+    fn inactive_file(&self) -> Option<Information> {
+        self.as_ref().size("Inactive(file)")
+    }
+
+    fn unevictable(&self) -> Option<Information> {
+        self.as_ref().size("Unevictable")
+    }
+
+    fn mlocked(&self) -> Option<Information> {
+        self.as_ref().size("Mlocked")
+    }
+
+    fn dirty(&self) -> Option<Information> {
+        self.as_ref().size("Dirty")
+    }
+
+    fn writeback(&self) -> Option<Information> {
+        self.as_ref().size("Writeback")
+    }
+
+    fn anon_pages(&self) -> Option<Information> {
+        self.as_ref().size("AnonPages")
+    }
+
+    fn mapped(&self) -> Option<Information> {
+        self.as_ref().size("Mapped")
+    }
+
+    fn kernel_reclaimable(&self) -> Option<Information> {
+        self.as_ref().size("KReclaimable")
+    }
+
+    fn slab(&self) -> Option<Information> {
+        self.as_ref().size("Slab")
+    }
+
+    fn slab_reclaimable(&self) -> Option<Information> {
+        self.as_ref().size("SReclaimable")
+    }
+
+    fn slab_unreclaimable(&self) -> Option<Information> {
+        self.as_ref().size("SUnreclaim")
+    }
+
+    fn kernel_stack(&self) -> Option<Information> {
+        self.as_ref().size("KernelStack")
+    }
+
+    fn page_tables(&self) -> Option<Information> {
+        self.as_ref().size("PageTables")
+    }
+
+    fn nfs_unstable(&self) -> Option<Information> {
+        self.as_ref().size("NFS_Unstable")
+    }
+
+    fn bounce(&self) -> Option<Information> {
+        self.as_ref().size("Bounce")
+    }
+
+    fn writeback_tmp(&self) -> Option<Information> {
+        self.as_ref().size("WritebackTmp")
+    }
+
+    fn commit_limit(&self) -> Option<Information> {
+        self.as_ref().size("CommitLimit")
+    }
+
+    fn committed_as(&self) -> Option<Information> {
+        self.as_ref().size("Committed_AS")
+    }
+
+    fn vmalloc_total(&self) -> Option<Information> {
+        self.as_ref().size("VmallocTotal")
+    }
+
+    fn vmalloc_used(&self) -> Option<Information> {
+        self.as_ref().size("VmallocUsed")
+    }
+
+    fn percpu(&self) -> Option<Information> {
+        self.as_ref().size("Percpu")
+    }
+
+    fn hardware_corrupted(&self) -> Option<Information> {
+        self.as_ref().size("HardwareCorrupted")
+    }
+
+    fn anon_huge_pages(&self) -> Option<Information> {
+        self.as_ref().size("AnonHugePages")
+    }
+
+    fn shmem_huge_pages(&self) -> Option<Information> {
+        self.as_ref().size("ShmemHugePages")
+    }
+
+    fn file_huge_pages(&self) -> Option<Information> {
+        self.as_ref().size("FileHugePages")
+    }
+
+    fn huge_pages_total(&self) -> Option<u64> {
+        self.as_ref().count("HugePages_Total")
+    }
+
+    fn huge_pages_free(&self) -> Option<u64> {
+        self.as_ref().count("HugePages_Free")
+    }
+
+    fn huge_pages_reserved(&self) -> Option<u64> {
+        self.as_ref().count("HugePages_Rsvd")
+    }
+
+    fn huge_pages_surplus(&self) -> Option<u64> {
+        self.as_ref().count("HugePages_Surp")
+    }
+
+    fn huge_page_size(&self) -> Option<Information> {
+        self.as_ref().size("Hugepagesize")
+    }
+
+    fn hugetlb(&self) -> Option<Information> {
+        self.as_ref().size("Hugetlb")
+    }
+
+    fn get(&self, key: &str) -> Option<Information> {
+        self.as_ref().size(key)
+    }
+}
@@ -0,0 +1,25 @@
+//! Linux-specific extensions
+
+mod buddyinfo;
+mod cgroup;
+mod hugepages;
+mod ksm;
+mod memory;
+mod numa;
+mod pressure;
+mod swap;
+mod vmstat;
+mod zram;
+mod zswap;
+
+pub use self::buddyinfo::*;
+pub use self::cgroup::*;
+pub use self::hugepages::*;
+pub use self::ksm::*;
+pub use self::memory::*;
+pub use self::numa::*;
+pub use self::pressure::*;
+pub use self::swap::*;
+pub use self::vmstat::*;
+pub use self::zram::*;
+pub use self::zswap::*;
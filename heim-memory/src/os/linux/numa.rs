@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use heim_common::prelude::*;
+use heim_common::units::{information, Information};
+
+use super::HugePages;
+#[cfg(target_os = "linux")]
+use crate::sys;
+
+/// NUMA node memory statistics.
+///
+/// Values are read from the `/sys/devices/system/node/node*/` directories.
+#[derive(Debug, Clone)]
+pub struct NumaNode {
+    pub(crate) id: u32,
+    pub(crate) meminfo: HashMap<String, Information>,
+    pub(crate) numastat: HashMap<String, u64>,
+    pub(crate) distances: Vec<u32>,
+    pub(crate) cpus: Vec<u32>,
+    pub(crate) huge_pages: Vec<HugePages>,
+}
+
+impl NumaNode {
+    /// Returns NUMA node number.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the total amount of physical memory attached to this node.
+    pub fn total(&self) -> Information {
+        self.size_or_zero("MemTotal")
+    }
+
+    /// Returns the amount of free memory on this node.
+    pub fn free(&self) -> Information {
+        self.size_or_zero("MemFree")
+    }
+
+    /// Returns the amount of memory used for the page cache on this node.
+    pub fn file_pages(&self) -> Option<Information> {
+        self.get("FilePages")
+    }
+
+    /// Returns the amount of non-file backed memory on this node.
+    pub fn anon_pages(&self) -> Option<Information> {
+        self.get("AnonPages")
+    }
+
+    /// Returns value for any key from the node `meminfo` file expressed in memory units,
+    /// for example `Dirty` or `Slab`.
+    pub fn get(&self, key: &str) -> Option<Information> {
+        self.meminfo.get(key).copied()
+    }
+
+    /// Returns number of pages allocated on this node as it was intended.
+    pub fn numa_hit(&self) -> Option<u64> {
+        self.numastat.get("numa_hit").copied()
+    }
+
+    /// Returns number of pages allocated on this node despite the process preferring
+    /// some other node.
+    pub fn numa_miss(&self) -> Option<u64> {
+        self.numastat.get("numa_miss").copied()
+    }
+
+    /// Returns number of pages intended for this node, but allocated on some other node.
+    pub fn numa_foreign(&self) -> Option<u64> {
+        self.numastat.get("numa_foreign").copied()
+    }
+
+    /// Returns number of interleave policy pages successfully allocated on this node.
+    pub fn interleave_hit(&self) -> Option<u64> {
+        self.numastat.get("interleave_hit").copied()
+    }
+
+    /// Returns number of pages allocated on this node while the process was running on it.
+    pub fn local_node(&self) -> Option<u64> {
+        self.numastat.get("local_node").copied()
+    }
+
+    /// Returns number of pages allocated on this node while the process was running
+    /// on some other node.
+    pub fn other_node(&self) -> Option<u64> {
+        self.numastat.get("other_node").copied()
+    }
+
+    /// Returns relative distances from this node to all online nodes,
+    /// ordered by node number.
+    ///
+    /// Distance from the node to itself is usually equal to `10`.
+    pub fn distances(&self) -> &[u32] {
+        &self.distances
+    }
+
+    /// Returns logical CPU numbers which belong to this node.
+    pub fn cpus(&self) -> &[u32] {
+        &self.cpus
+    }
+
+    /// Returns huge pages pools allocated on this node, ordered by page size.
+    pub fn huge_pages(&self) -> &[HugePages] {
+        &self.huge_pages
+    }
+
+    fn size_or_zero(&self, key: &str) -> Information {
+        self.get(key)
+            .unwrap_or_else(|| Information::new::<information::byte>(0))
+    }
+}
+
+/// Returns a stream over [NUMA nodes] available in the system.
+///
+/// Stream is ordered by the node number.
+///
+/// ## Compatibility
+///
+/// If kernel was built without NUMA support, stream will be empty.
+///
+/// [NUMA nodes]: ./struct.NumaNode.html
+pub async fn numa_nodes() -> Result<impl Stream<Item = Result<NumaNode>>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::numa_nodes().await
+        } else {
+            Ok(stream::iter(vec![]))
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use heim_common::prelude::*;
+
+#[cfg(target_os = "linux")]
+use crate::sys;
+
+/// Memory pressure event.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum PressureEvent {
+    /// Tasks were stalled waiting for memory longer than the configured threshold.
+    ThresholdExceeded,
+    /// OOM killer was invoked, contains number of processes killed since the previous check.
+    OomKill(u64),
+    /// Memory usage exceeded the cgroup v2 `memory.high` boundary
+    /// and processes were throttled, contains number of times it happened since the previous check.
+    HighLimit(u64),
+    /// Memory usage hit the cgroup v2 `memory.max` limit,
+    /// contains number of times it happened since the previous check.
+    MaxLimit(u64),
+}
+
+/// Returns a stream which yields [memory pressure events] as they happen.
+///
+/// `ThresholdExceeded` event is yielded when tasks were stalled waiting for memory
+/// for `stall` time (cumulatively) during any `window` period.
+///
+/// If the current process belongs to the cgroup v2 memory controller,
+/// its `memory.pressure` and `memory.events` files are monitored,
+/// otherwise system-wide `/proc/pressure/memory` file and the `oom_kill` counter
+/// from the `/proc/vmstat` file are used.
+///
+/// Pressure stall information (PSI) triggers and `memory.events` file modification notifications
+/// are used to wake up only when something happens; if they are not available
+/// (ex. for kernels older than 5.2 or if process is not allowed to create a trigger),
+/// stream falls back to polling with the `window` interval, which is shortened
+/// while events keep coming.
+///
+/// Stream is infinite and ends only after yielding an error.
+///
+/// ## Errors
+///
+/// Returns an `InvalidInput` error if `stall` is longer than `window`.
+///
+/// ## Compatibility
+///
+/// `ThresholdExceeded` events require Linux 4.20 or newer with PSI enabled,
+/// for older versions only `OomKill` events are yielded (Linux 4.13 or newer).
+///
+/// [memory pressure events]: ./enum.PressureEvent.html
+pub async fn memory_pressure(
+    stall: Duration,
+    window: Duration,
+) -> Result<impl Stream<Item = Result<PressureEvent>>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::memory_pressure(stall, window).await
+        } else {
+            let _ = (stall, window);
+
+            Ok(stream::iter(vec![]))
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::path::{Path, PathBuf};
+
+use heim_common::prelude::*;
+use heim_common::units::Information;
+
+use super::ZramMmStat;
+#[cfg(target_os = "linux")]
+use crate::sys;
+
+/// Swap area type.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SwapKind {
+    /// Block device partition.
+    Partition,
+    /// Regular file.
+    File,
+    /// Compressed in-memory zram block device.
+    Zram,
+}
+
+/// Swap area, either a block device or a file.
+///
+/// Values are read from the `/proc/swaps` file.
+#[derive(Debug, Clone)]
+pub struct SwapDevice {
+    pub(crate) path: PathBuf,
+    pub(crate) kind: SwapKind,
+    pub(crate) size: Information,
+    pub(crate) used: Information,
+    pub(crate) priority: i32,
+    pub(crate) zram: Option<ZramMmStat>,
+}
+
+impl SwapDevice {
+    /// Returns path to the swap block device or file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns swap area type.
+    pub fn kind(&self) -> SwapKind {
+        self.kind
+    }
+
+    /// Returns total swap area size.
+    pub fn size(&self) -> Information {
+        self.size
+    }
+
+    /// Returns used swap space.
+    pub fn used(&self) -> Information {
+        self.used
+    }
+
+    /// Returns free swap space.
+    pub fn free(&self) -> Information {
+        self.size - self.used
+    }
+
+    /// Returns swap area priority, areas with higher priority are used first.
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    /// Returns compression statistics for zram swap areas.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` for any other swap area types
+    /// or if statistics are not available (Linux 4.1 and newer are required).
+    pub fn zram(&self) -> Option<&ZramMmStat> {
+        self.zram.as_ref()
+    }
+}
+
+/// Returns a stream over active [swap areas].
+///
+/// Stream is ordered in the same way as the `/proc/swaps` file.
+///
+/// ## Compatibility
+///
+/// If kernel was built without swap support, stream will be empty.
+///
+/// [swap areas]: ./struct.SwapDevice.html
+pub async fn swap_devices() -> Result<impl Stream<Item = Result<SwapDevice>>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::swap_devices().await
+        } else {
+            Ok(stream::iter(vec![]))
+        }
+    }
+}
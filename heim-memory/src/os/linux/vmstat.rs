@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::ops;
+
+use heim_common::prelude::*;
+
+#[cfg(target_os = "linux")]
+use crate::sys;
+
+/// Virtual memory statistics.
+///
+/// Provides well-known counters from the `/proc/vmstat` file
+/// and access to all other raw counters exposed by the running kernel.
+///
+/// Counters are cumulative since boot, subtract two samples
+/// in order to get values for the sampling window and calculate rates:
+///
+/// ```rust,no_run
+/// # use std::time::Duration;
+/// # use heim_common::prelude::*;
+/// # use heim_memory::os::linux::vmstat;
+/// #
+/// # #[heim_derive::main]
+/// # async fn main() -> Result<()> {
+/// let before = vmstat().await?;
+/// smol::Timer::after(Duration::from_secs(1)).await;
+/// let delta = vmstat().await? - before;
+///
+/// println!("Major page faults per second: {:?}", delta.major_page_faults());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct VmStat {
+    pub(crate) counters: HashMap<String, u64>,
+}
+
+impl VmStat {
+    /// Returns raw counter value by the `/proc/vmstat` key name.
+    pub fn get(&self, key: &str) -> Option<u64> {
+        self.counters.get(key).copied()
+    }
+
+    /// Returns all raw counters exposed by the kernel.
+    pub fn counters(&self) -> &HashMap<String, u64> {
+        &self.counters
+    }
+
+    /// Returns number of page faults, both minor and major ones (`pgfault`).
+    pub fn page_faults(&self) -> Option<u64> {
+        self.get("pgfault")
+    }
+
+    /// Returns number of major page faults, which required disk I/O (`pgmajfault`).
+    pub fn major_page_faults(&self) -> Option<u64> {
+        self.get("pgmajfault")
+    }
+
+    /// Returns number of kilobytes the system has paged in from disk (`pgpgin`).
+    pub fn paged_in(&self) -> Option<u64> {
+        self.get("pgpgin")
+    }
+
+    /// Returns number of kilobytes the system has paged out to disk (`pgpgout`).
+    pub fn paged_out(&self) -> Option<u64> {
+        self.get("pgpgout")
+    }
+
+    /// Returns number of pages the system has swapped in from disk (`pswpin`).
+    pub fn swapped_in(&self) -> Option<u64> {
+        self.get("pswpin")
+    }
+
+    /// Returns number of pages the system has swapped out to disk (`pswpout`).
+    pub fn swapped_out(&self) -> Option<u64> {
+        self.get("pswpout")
+    }
+
+    /// Returns number of pages scanned by the `kswapd` background reclaim (`pgscan_kswapd`).
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.8, older versions always return `None`.
+    pub fn pages_scanned_kswapd(&self) -> Option<u64> {
+        self.get("pgscan_kswapd")
+    }
+
+    /// Returns number of pages scanned by the direct reclaim (`pgscan_direct`).
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.8, older versions always return `None`.
+    pub fn pages_scanned_direct(&self) -> Option<u64> {
+        self.get("pgscan_direct")
+    }
+
+    /// Returns number of pages reclaimed by the `kswapd` background reclaim (`pgsteal_kswapd`).
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.8, older versions always return `None`.
+    pub fn pages_stolen_kswapd(&self) -> Option<u64> {
+        self.get("pgsteal_kswapd")
+    }
+
+    /// Returns number of pages reclaimed by the direct reclaim (`pgsteal_direct`).
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.8, older versions always return `None`.
+    pub fn pages_stolen_direct(&self) -> Option<u64> {
+        self.get("pgsteal_direct")
+    }
+
+    /// Returns number of times tasks entered the direct reclaim.
+    ///
+    /// Since Linux 4.10 this counter is split by memory zones,
+    /// in that case per-zone `allocstall_*` values are summed.
+    pub fn alloc_stalls(&self) -> Option<u64> {
+        self.get("allocstall").or_else(|| {
+            let mut zones = self
+                .counters
+                .iter()
+                .filter(|(key, _)| key.starts_with("allocstall_"))
+                .peekable();
+            let _ = zones.peek()?;
+
+            Some(zones.map(|(_, value)| value).sum())
+        })
+    }
+
+    /// Returns number of processes killed by the OOM killer (`oom_kill`).
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.13, older versions always return `None`.
+    pub fn oom_kills(&self) -> Option<u64> {
+        self.get("oom_kill")
+    }
+
+    /// Returns number of times tasks stalled waiting for the memory compaction (`compact_stall`).
+    pub fn compact_stalls(&self) -> Option<u64> {
+        self.get("compact_stall")
+    }
+
+    /// Returns number of failed memory compaction attempts (`compact_fail`).
+    pub fn compact_failures(&self) -> Option<u64> {
+        self.get("compact_fail")
+    }
+
+    /// Returns number of successful memory compaction attempts (`compact_success`).
+    pub fn compact_successes(&self) -> Option<u64> {
+        self.get("compact_success")
+    }
+
+    /// Returns number of transparent huge pages allocated on page fault (`thp_fault_alloc`).
+    pub fn thp_fault_allocs(&self) -> Option<u64> {
+        self.get("thp_fault_alloc")
+    }
+
+    /// Returns number of page faults which failed to allocate transparent huge page
+    /// and fell back to regular pages (`thp_fault_fallback`).
+    pub fn thp_fault_fallbacks(&self) -> Option<u64> {
+        self.get("thp_fault_fallback")
+    }
+
+    /// Returns number of transparent huge pages allocated by `khugepaged`
+    /// to collapse regular pages (`thp_collapse_alloc`).
+    pub fn thp_collapse_allocs(&self) -> Option<u64> {
+        self.get("thp_collapse_alloc")
+    }
+
+    /// Returns number of transparent huge pages split into regular pages (`thp_split_page`).
+    pub fn thp_splits(&self) -> Option<u64> {
+        self.get("thp_split_page")
+    }
+}
+
+impl ops::Sub<VmStat> for VmStat {
+    type Output = VmStat;
+
+    /// Calculates difference between two samples.
+    ///
+    /// Counters missing in any of the samples are skipped.
+    /// Note that `nr_*` keys represent current values and not the cumulative counters,
+    /// so their differences are not meaningful.
+    fn sub(self, rhs: VmStat) -> VmStat {
+        let counters = self
+            .counters
+            .into_iter()
+            .filter_map(|(key, value)| {
+                rhs.counters
+                    .get(&key)
+                    .map(|previous| (key, value.wrapping_sub(*previous)))
+            })
+            .collect();
+
+        VmStat { counters }
+    }
+}
+
+/// Returns [virtual memory statistics].
+///
+/// [virtual memory statistics]: ./struct.VmStat.html
+pub async fn vmstat() -> Result<VmStat> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::vmstat().await
+        } else {
+            unimplemented!("For documentation rendering")
+        }
+    }
+}
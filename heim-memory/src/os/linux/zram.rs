@@ -0,0 +1,189 @@
+use heim_common::prelude::*;
+use heim_common::units::{information, ratio, Information, Ratio};
+
+#[cfg(target_os = "linux")]
+use crate::sys;
+
+/// zram device memory statistics.
+///
+/// Values are read from the `/sys/block/zram*/mm_stat` file.
+#[derive(Debug, Clone)]
+pub struct ZramMmStat {
+    pub(crate) orig_data_size: Information,
+    pub(crate) compr_data_size: Information,
+    pub(crate) mem_used_total: Information,
+    pub(crate) mem_limit: Option<Information>,
+    pub(crate) mem_used_max: Information,
+    pub(crate) same_pages: u64,
+    pub(crate) pages_compacted: u64,
+    pub(crate) huge_pages: Option<u64>,
+}
+
+impl ZramMmStat {
+    /// Returns uncompressed size of data stored in this device.
+    pub fn original_size(&self) -> Information {
+        self.orig_data_size
+    }
+
+    /// Returns compressed size of data stored in this device.
+    pub fn compressed_size(&self) -> Information {
+        self.compr_data_size
+    }
+
+    /// Returns the amount of memory allocated for this device,
+    /// including allocator fragmentation and metadata overhead.
+    pub fn memory_used(&self) -> Information {
+        self.mem_used_total
+    }
+
+    /// Returns the maximum amount of memory this device can use.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if limit is not set.
+    pub fn memory_limit(&self) -> Option<Information> {
+        self.mem_limit
+    }
+
+    /// Returns the maximum amount of memory this device has consumed to store the data.
+    pub fn memory_used_max(&self) -> Information {
+        self.mem_used_max
+    }
+
+    /// Returns number of same element filled pages written to this device,
+    /// no memory is allocated for them.
+    pub fn same_pages(&self) -> u64 {
+        self.same_pages
+    }
+
+    /// Returns number of pages freed during the memory compaction.
+    pub fn pages_compacted(&self) -> u64 {
+        self.pages_compacted
+    }
+
+    /// Returns number of incompressible pages stored in this device.
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.19, older versions always return `None`.
+    pub fn huge_pages(&self) -> Option<u64> {
+        self.huge_pages
+    }
+
+    /// Returns compression ratio, original data size divided by the memory used.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if device does not use any memory yet.
+    pub fn compression_ratio(&self) -> Option<Ratio> {
+        let used = self.mem_used_total.get::<information::byte>();
+        if used > 0 {
+            let original = self.orig_data_size.get::<information::byte>();
+
+            Some(Ratio::new::<ratio::ratio>(original as f32 / used as f32))
+        } else {
+            None
+        }
+    }
+}
+
+/// zram device I/O error statistics.
+///
+/// Values are read from the `/sys/block/zram*/io_stat` file.
+#[derive(Debug, Clone)]
+pub struct ZramIoStat {
+    pub(crate) failed_reads: u64,
+    pub(crate) failed_writes: u64,
+    pub(crate) invalid_io: u64,
+    pub(crate) notify_free: u64,
+}
+
+impl ZramIoStat {
+    /// Returns number of failed reads.
+    pub fn failed_reads(&self) -> u64 {
+        self.failed_reads
+    }
+
+    /// Returns number of failed writes.
+    pub fn failed_writes(&self) -> u64 {
+        self.failed_writes
+    }
+
+    /// Returns number of non-page-size-aligned I/O requests.
+    pub fn invalid_io(&self) -> u64 {
+        self.invalid_io
+    }
+
+    /// Returns number of pages freed because of the swap slot free notifications.
+    pub fn notify_free(&self) -> u64 {
+        self.notify_free
+    }
+}
+
+/// zram compressed RAM block device.
+///
+/// Values are read from the `/sys/block/zram*/` directories.
+#[derive(Debug, Clone)]
+pub struct ZramDevice {
+    pub(crate) name: String,
+    pub(crate) disk_size: Information,
+    pub(crate) algorithm: Option<String>,
+    pub(crate) mm_stat: Option<ZramMmStat>,
+    pub(crate) io_stat: Option<ZramIoStat>,
+}
+
+impl ZramDevice {
+    /// Returns block device name, ex. `zram0`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns maximum amount of uncompressed data device can store.
+    ///
+    /// Zero size means that device is not initialized yet.
+    pub fn disk_size(&self) -> Information {
+        self.disk_size
+    }
+
+    /// Returns selected compression algorithm, ex. `lzo-rle` or `zstd`.
+    pub fn algorithm(&self) -> Option<&str> {
+        self.algorithm.as_deref()
+    }
+
+    /// Returns device memory statistics.
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.1, older versions always return `None`.
+    pub fn mm_stat(&self) -> Option<&ZramMmStat> {
+        self.mm_stat.as_ref()
+    }
+
+    /// Returns device I/O error statistics.
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 4.1, older versions always return `None`.
+    pub fn io_stat(&self) -> Option<&ZramIoStat> {
+        self.io_stat.as_ref()
+    }
+}
+
+/// Returns a stream over [zram devices] available in the system.
+///
+/// Stream is ordered by the device number.
+///
+/// ## Compatibility
+///
+/// If `zram` module is not loaded, stream will be empty.
+///
+/// [zram devices]: ./struct.ZramDevice.html
+pub async fn zram_devices() -> Result<impl Stream<Item = Result<ZramDevice>>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::zram_devices().await
+        } else {
+            Ok(stream::iter(vec![]))
+        }
+    }
+}
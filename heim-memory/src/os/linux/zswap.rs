@@ -0,0 +1,113 @@
+use heim_common::prelude::*;
+use heim_common::units::Information;
+
+#[cfg(target_os = "linux")]
+use crate::sys;
+
+/// zswap compressed swap cache parameters and statistics.
+///
+/// Parameters are read from the `/sys/module/zswap/parameters/` directory,
+/// statistics are read from the `/proc/meminfo` and `/proc/vmstat` files,
+/// so `debugfs` is not required.
+#[derive(Debug, Clone)]
+pub struct Zswap {
+    pub(crate) enabled: bool,
+    pub(crate) compressor: Option<String>,
+    pub(crate) zpool: Option<String>,
+    pub(crate) max_pool_percent: Option<u64>,
+    pub(crate) accept_threshold_percent: Option<u64>,
+    pub(crate) pool_size: Option<Information>,
+    pub(crate) stored: Option<Information>,
+    pub(crate) stores: Option<u64>,
+    pub(crate) loads: Option<u64>,
+}
+
+impl Zswap {
+    /// Returns `true` if zswap is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns compression algorithm name, ex. `lzo` or `zstd`.
+    pub fn compressor(&self) -> Option<&str> {
+        self.compressor.as_deref()
+    }
+
+    /// Returns memory allocator name used for the compressed pool, ex. `zbud` or `zsmalloc`.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if running kernel does not expose this parameter anymore.
+    pub fn zpool(&self) -> Option<&str> {
+        self.zpool.as_deref()
+    }
+
+    /// Returns maximum share of the physical memory the compressed pool can occupy, in percents.
+    pub fn max_pool_percent(&self) -> Option<u64> {
+        self.max_pool_percent
+    }
+
+    /// Returns pool usage threshold in percents of the maximum pool size,
+    /// at which zswap starts accepting pages again after the pool became full.
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 5.6, older versions always return `None`.
+    pub fn accept_threshold_percent(&self) -> Option<u64> {
+        self.accept_threshold_percent
+    }
+
+    /// Returns memory consumed by the compressed pool (`Zswap` key in the `/proc/meminfo`).
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 5.19, older versions always return `None`.
+    pub fn pool_size(&self) -> Option<Information> {
+        self.pool_size
+    }
+
+    /// Returns uncompressed size of data stored in the pool
+    /// (`Zswapped` key in the `/proc/meminfo`).
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 5.19, older versions always return `None`.
+    pub fn stored(&self) -> Option<Information> {
+        self.stored
+    }
+
+    /// Returns number of pages stored into the pool (`zswpout` key in the `/proc/vmstat`).
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 5.19, older versions always return `None`.
+    pub fn stores(&self) -> Option<u64> {
+        self.stores
+    }
+
+    /// Returns number of pages loaded from the pool (`zswpin` key in the `/proc/vmstat`).
+    ///
+    /// ## Compatibility
+    ///
+    /// Available since Linux 5.19, older versions always return `None`.
+    pub fn loads(&self) -> Option<u64> {
+        self.loads
+    }
+}
+
+/// Returns [zswap] parameters and statistics.
+///
+/// ## Returns
+///
+/// Returns `Ok(None)` if kernel was built without zswap support.
+///
+/// [zswap]: ./struct.Zswap.html
+pub async fn zswap() -> Result<Option<Zswap>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::zswap().await
+        } else {
+            unimplemented!("For documentation rendering")
+        }
+    }
+}
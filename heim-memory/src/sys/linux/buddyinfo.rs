@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::fs;
+
+use heim_common::prelude::*;
+use heim_runtime as rt;
+
+use crate::os::linux::{BuddyInfo, Watermarks};
+
+type ZoneKey = (u32, String);
+
+// Parses `Node 0, zone      DMA` line prefix parts
+fn zone_key(node: &str, zone: &str) -> Result<ZoneKey> {
+    let node = node.trim_end_matches(',').parse::<u32>()?;
+
+    Ok((node, zone.trim_end_matches(',').to_string()))
+}
+
+fn parse_counts<'a, T>(values: T) -> Result<Vec<u64>>
+where
+    T: Iterator<Item = &'a str>,
+{
+    values
+        // `/proc/pagetypeinfo` caps large values and prints them as `>100000`
+        .map(|value| value.trim_start_matches('>').parse::<u64>().map_err(Error::from))
+        .collect()
+}
+
+// Example:
+//
+// Node 0, zone      DMA      0      0      0      0      0      0      0      0      1      1      3
+// Node 0, zone    DMA32   4038    581    131     32     21     10      4      2      6      3    167
+pub fn parse_buddyinfo(contents: &str) -> Result<Vec<(ZoneKey, Vec<u64>)>> {
+    let mut zones = Vec::new();
+    for line in contents.lines() {
+        let parts = line.split_whitespace().collect::<Vec<_>>();
+        match parts.as_slice() {
+            ["Node", node, "zone", zone, counts @ ..] => {
+                zones.push((zone_key(node, zone)?, parse_counts(counts.iter().copied())?));
+            }
+            _ => continue,
+        }
+    }
+
+    Ok(zones)
+}
+
+// Example:
+//
+// Free pages count per migrate type at order       0      1      2      3 ...
+// Node    0, zone      DMA, type    Unmovable      0      0      0      0 ...
+// Node    0, zone      DMA, type      Movable      0      0      0      0 ...
+//
+// Number of blocks type     Unmovable      Movable  Reclaimable ...
+// Node 0, zone      DMA            1            7            0 ...
+pub fn parse_pagetypeinfo(contents: &str) -> Result<HashMap<ZoneKey, HashMap<String, Vec<u64>>>> {
+    let mut zones: HashMap<ZoneKey, HashMap<String, Vec<u64>>> = HashMap::new();
+    for line in contents.lines() {
+        let parts = line.split_whitespace().collect::<Vec<_>>();
+        match parts.as_slice() {
+            ["Node", node, "zone", zone, "type", kind, counts @ ..] => {
+                let _ = zones
+                    .entry(zone_key(node, zone)?)
+                    .or_default()
+                    .insert(kind.to_string(), parse_counts(counts.iter().copied())?);
+            }
+            _ => continue,
+        }
+    }
+
+    Ok(zones)
+}
+
+// Example:
+//
+// Node 0, zone   Normal
+//   per-node stats
+//       nr_inactive_anon 47998
+//   ...
+//   pages free     10059
+//         boost    0
+//         min      8340
+//         low      10425
+//         high     12510
+//         spanned  262144
+//         present  262144
+//         managed  758676
+pub fn parse_zoneinfo(contents: &str) -> Result<HashMap<ZoneKey, Watermarks>> {
+    let mut zones = HashMap::new();
+    let mut current: Option<(ZoneKey, Watermarks)> = None;
+
+    for line in contents.lines() {
+        let parts = line.split_whitespace().collect::<Vec<_>>();
+        if let ["Node", node, "zone", zone] = parts.as_slice() {
+            if let Some((key, watermarks)) = current.take() {
+                let _ = zones.insert(key, watermarks);
+            }
+            current = Some((zone_key(node, zone)?, Watermarks::default()));
+            continue;
+        }
+
+        let (_, watermarks) = match current.as_mut() {
+            Some(current) => current,
+            None => continue,
+        };
+        match parts.as_slice() {
+            ["pages", "free", value] => watermarks.free = value.parse()?,
+            ["min", value] => watermarks.min = value.parse()?,
+            ["low", value] => watermarks.low = value.parse()?,
+            ["high", value] => watermarks.high = value.parse()?,
+            ["managed", value] => watermarks.managed = Some(value.parse()?),
+            _ => continue,
+        }
+    }
+
+    if let Some((key, watermarks)) = current {
+        let _ = zones.insert(key, watermarks);
+    }
+
+    Ok(zones)
+}
+
+pub async fn buddyinfo() -> Result<impl Stream<Item = Result<BuddyInfo>>> {
+    let zones = rt::spawn_blocking(|| {
+        let root = rt::linux::procfs_root();
+
+        let path = root.join("buddyinfo");
+        let buddyinfo = fs::read_to_string(&path).map_err(|e| Error::from(e).with_file(&path))?;
+        let zones = parse_buddyinfo(&buddyinfo).map_err(|e| e.with_file(&path))?;
+
+        // Since Linux 5.5 `/proc/pagetypeinfo` is readable by root only
+        let path = root.join("pagetypeinfo");
+        let mut types = match fs::read_to_string(&path) {
+            Ok(contents) => {
+                let types = parse_pagetypeinfo(&contents).map_err(|e| e.with_file(&path))?;
+                Some(types)
+            }
+            Err(..) => None,
+        };
+        let path = root.join("zoneinfo");
+        let mut watermarks = match fs::read_to_string(&path) {
+            Ok(contents) => parse_zoneinfo(&contents).map_err(|e| e.with_file(&path))?,
+            Err(..) => HashMap::new(),
+        };
+
+        Ok::<_, Error>(
+            zones
+                .into_iter()
+                .map(|(key, free_blocks)| {
+                    let free_blocks_by_type = types
+                        .as_mut()
+                        .map(|types| types.remove(&key).unwrap_or_default());
+
+                    Ok(BuddyInfo {
+                        watermarks: watermarks.remove(&key),
+                        node: key.0,
+                        zone: key.1,
+                        free_blocks,
+                        free_blocks_by_type,
+                    })
+                })
+                .collect::<Vec<_>>(),
+        )
+    })
+    .await?;
+
+    Ok(stream::iter(zones))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_buddyinfo, parse_pagetypeinfo, parse_zoneinfo};
+
+    #[test]
+    fn test_parse_buddyinfo() {
+        let zones = parse_buddyinfo(
+            "Node 0, zone      DMA      0      0      0      0      0      0      0      0      1      1      3
+Node 1, zone   Normal      5   1943    160     29     15     10      4      1      1      4      2
+",
+        )
+        .unwrap();
+
+        assert_eq!(zones.len(), 2);
+        assert_eq!(zones[1].0, (1, "Normal".to_string()));
+        assert_eq!(zones[1].1[1], 1943);
+    }
+
+    #[test]
+    fn test_parse_pagetypeinfo() {
+        let zones = parse_pagetypeinfo(
+            "Page block order: 9
+Pages per block:  512
+
+Free pages count per migrate type at order       0      1      2
+Node    0, zone    DMA32, type    Unmovable    193     64     13
+Node    0, zone    DMA32, type      Movable >100000   2113    733
+
+Number of blocks type     Unmovable      Movable  Reclaimable
+Node 0, zone    DMA32           25          485           14
+",
+        )
+        .unwrap();
+
+        let types = &zones[&(0, "DMA32".to_string())];
+        assert_eq!(types.len(), 2);
+        assert_eq!(types["Movable"], vec![100_000, 2113, 733]);
+    }
+
+    #[test]
+    fn test_parse_zoneinfo() {
+        let zones = parse_zoneinfo(
+            "Node 0, zone      DMA
+  per-node stats
+      nr_inactive_anon 47998
+  pages free     3840
+        boost    0
+        min      42
+        low      52
+        high     62
+        spanned  4095
+        managed  3840
+  pagesets
+    cpu: 0
+              count: 0
+              high:  0
+Node 0, zone    DMA32
+  pages free     199216
+        min      8512
+        low      10640
+        high     12768
+",
+        )
+        .unwrap();
+
+        let dma = &zones[&(0, "DMA".to_string())];
+        assert_eq!(dma.free(), 3840);
+        assert_eq!(dma.min(), 42);
+        assert_eq!(dma.high(), 62);
+        assert_eq!(dma.managed(), Some(3840));
+
+        let dma32 = &zones[&(0, "DMA32".to_string())];
+        assert_eq!(dma32.low(), 10640);
+        assert_eq!(dma32.managed(), None);
+    }
+}
@@ -0,0 +1,94 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use heim_common::prelude::*;
+use heim_common::sys::linux::cgroup::{self, source, Cgroup, LimitSource, Version};
+use heim_common::units::{information, Information};
+use heim_runtime as rt;
+
+use crate::os::linux::ContainerMemory;
+
+// Returns `None` for missing files and for the `max` value,
+// which stands for "no limit" in cgroup v2.
+fn read_bytes(path: &Path) -> Result<Option<u64>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(..) => return Ok(None),
+    };
+
+    match contents.trim() {
+        "max" => Ok(None),
+        value => value
+            .parse::<u64>()
+            .map(Some)
+            .map_err(|e| Error::from(e).with_file(path)),
+    }
+}
+
+fn limit(group: &Cgroup) -> Result<Option<(u64, PathBuf)>> {
+    let filename = match group.version() {
+        Version::V1 => "memory.limit_in_bytes",
+        Version::V2 => "memory.max",
+    };
+
+    let mut lowest: Option<(u64, PathBuf)> = None;
+    for dir in group.ancestors() {
+        let path = dir.join(filename);
+        if let Some(value) = read_bytes(&path)? {
+            match lowest {
+                Some((current, _)) if current <= value => {}
+                _ => lowest = Some((value, path)),
+            }
+        }
+    }
+
+    Ok(lowest)
+}
+
+fn usage(group: &Cgroup) -> Result<Option<(u64, PathBuf)>> {
+    let path = group.path().join(match group.version() {
+        Version::V1 => "memory.usage_in_bytes",
+        Version::V2 => "memory.current",
+    });
+
+    Ok(read_bytes(&path)?.map(|value| (value, path)))
+}
+
+pub async fn container_memory() -> Result<ContainerMemory> {
+    let host = super::memory().await?;
+    let host_total = host.total();
+
+    let group = rt::spawn_blocking(|| {
+        let group = match cgroup::cgroup(rt::linux::procfs_root(), "memory")? {
+            Some(group) => group,
+            None => return Ok(None),
+        };
+
+        Ok::<_, Error>(Some((group.version(), limit(&group)?, usage(&group)?)))
+    })
+    .await?;
+
+    let mut memory = ContainerMemory {
+        limit: host_total,
+        limit_source: LimitSource::Host,
+        usage: host_total - host.available(),
+        usage_source: LimitSource::Host,
+    };
+
+    if let Some((version, limit, usage)) = group {
+        if let Some((value, path)) = limit {
+            // cgroup v1 reports huge page-aligned number when limit is not set
+            let value = Information::new::<information::byte>(value);
+            if value < host_total {
+                memory.limit = value;
+                memory.limit_source = source(version, path);
+            }
+        }
+        if let Some((value, path)) = usage {
+            memory.usage = Information::new::<information::byte>(value);
+            memory.usage_source = source(version, path);
+        }
+    }
+
+    Ok(memory)
+}
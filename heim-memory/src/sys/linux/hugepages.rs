@@ -0,0 +1,155 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use heim_common::prelude::*;
+use heim_common::sys::linux::parse_selected;
+use heim_common::units::{information, Information};
+use heim_runtime as rt;
+
+use crate::os::linux::{HugePages, ThpDefrag, ThpEnabled, TransparentHugePages};
+
+// Parses pool directory name, ex. `hugepages-2048kB`
+fn page_size(name: &str) -> Option<Information> {
+    let size = name.strip_prefix("hugepages-")?.strip_suffix("kB")?;
+
+    // Same as in `/proc/meminfo`, sizes are expressed in kilobytes
+    size.parse()
+        .ok()
+        .map(Information::new::<information::kilobyte>)
+}
+
+fn read_count(path: &Path) -> Result<u64> {
+    let contents = fs::read_to_string(path).map_err(|e| Error::from(e).with_file(path))?;
+
+    contents
+        .trim()
+        .parse()
+        .map_err(|e| Error::from(e).with_file(path))
+}
+
+fn pool(page_size: Information, path: &Path) -> Result<HugePages> {
+    // Per-node pools are not tracking the reserved pages
+    let reserved = path.join("resv_hugepages");
+    let reserved = if reserved.exists() {
+        Some(read_count(&reserved)?)
+    } else {
+        None
+    };
+
+    Ok(HugePages {
+        page_size,
+        total: read_count(&path.join("nr_hugepages"))?,
+        free: read_count(&path.join("free_hugepages"))?,
+        reserved,
+        surplus: read_count(&path.join("surplus_hugepages"))?,
+    })
+}
+
+// Reads all pools from the `hugepages/` directory, ordered by page size.
+pub fn read_pools(root: &Path) -> Result<Vec<Result<HugePages>>> {
+    let entries = match fs::read_dir(root) {
+        Ok(entries) => entries,
+        // Kernel was built without `CONFIG_HUGETLBFS`
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(Error::from(e).with_file(root)),
+    };
+
+    let mut pools = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if let Some(size) = entry.file_name().to_str().and_then(page_size) {
+            pools.push((size, entry.path()));
+        }
+    }
+    pools.sort_unstable_by_key(|(size, _)| *size);
+
+    Ok(pools
+        .into_iter()
+        .map(|(size, path)| pool(size, &path))
+        .collect())
+}
+
+pub async fn huge_pages() -> Result<impl Stream<Item = Result<HugePages>>> {
+    let pools =
+        rt::spawn_blocking(|| read_pools(&rt::linux::sysfs_root().join("kernel/mm/hugepages")))
+            .await?;
+
+    Ok(stream::iter(pools))
+}
+
+fn parse_enabled(contents: &str) -> Option<ThpEnabled> {
+    match parse_selected(contents)? {
+        "always" => Some(ThpEnabled::Always),
+        "madvise" => Some(ThpEnabled::Madvise),
+        "never" => Some(ThpEnabled::Never),
+        _ => None,
+    }
+}
+
+fn parse_defrag(contents: &str) -> Option<ThpDefrag> {
+    match parse_selected(contents)? {
+        "always" => Some(ThpDefrag::Always),
+        "defer" => Some(ThpDefrag::Defer),
+        "defer+madvise" => Some(ThpDefrag::DeferMadvise),
+        "madvise" => Some(ThpDefrag::Madvise),
+        "never" => Some(ThpDefrag::Never),
+        _ => None,
+    }
+}
+
+pub async fn transparent_huge_pages() -> Result<TransparentHugePages> {
+    let meminfo = super::memory().await?;
+
+    let (enabled, defrag, page_size) = rt::spawn_blocking(|| {
+        let root = rt::linux::sysfs_root().join("kernel/mm/transparent_hugepage");
+        // All files are missing if kernel was built without `CONFIG_TRANSPARENT_HUGEPAGE`
+        let read = |name: &str| fs::read_to_string(root.join(name)).ok();
+
+        let enabled = read("enabled").as_deref().and_then(parse_enabled);
+        let defrag = read("defrag").as_deref().and_then(parse_defrag);
+        let page_size = read("hpage_pmd_size")
+            .and_then(|size| size.trim().parse().ok())
+            .map(Information::new::<information::byte>);
+
+        (enabled, defrag, page_size)
+    })
+    .await;
+
+    Ok(TransparentHugePages {
+        enabled,
+        defrag,
+        page_size,
+        anon: meminfo.size("AnonHugePages"),
+        shmem: meminfo.size("ShmemHugePages"),
+        file: meminfo.size("FileHugePages"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use heim_common::units::information;
+
+    use super::{page_size, parse_defrag, parse_enabled};
+    use crate::os::linux::{ThpDefrag, ThpEnabled};
+
+    #[test]
+    fn test_page_size() {
+        let size = page_size("hugepages-2048kB").unwrap();
+        assert_eq!(size.get::<information::kilobyte>(), 2048);
+        assert!(page_size("hugepages-2048").is_none());
+    }
+
+    #[test]
+    fn test_parse_modes() {
+        assert_eq!(
+            parse_enabled("always [madvise] never\n"),
+            Some(ThpEnabled::Madvise)
+        );
+        assert_eq!(
+            parse_defrag("always defer [defer+madvise] madvise never\n"),
+            Some(ThpDefrag::DeferMadvise)
+        );
+        assert_eq!(parse_enabled("always madvise never\n"), None);
+    }
+}
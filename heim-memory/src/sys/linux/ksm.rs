@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+
+use heim_common::prelude::*;
+use heim_runtime as rt;
+
+use crate::os::linux::Ksm;
+
+pub async fn ksm() -> Result<Option<Ksm>> {
+    rt::spawn_blocking(|| {
+        let root = rt::linux::sysfs_root().join("kernel/mm/ksm");
+        let entries = match fs::read_dir(&root) {
+            Ok(entries) => entries,
+            // Kernel was built without `CONFIG_KSM`
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(Error::from(e).with_file(root)),
+        };
+
+        let mut values = HashMap::new();
+        for entry in entries {
+            let entry = entry?;
+            let name = match entry.file_name().into_string() {
+                Ok(name) => name,
+                Err(..) => continue,
+            };
+            // Some files are write-only or contain non-numeric values,
+            // ex. `advisor_mode`, these are skipped
+            let value = fs::read_to_string(entry.path())
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok());
+            if let Some(value) = value {
+                let _ = values.insert(name, value);
+            }
+        }
+
+        Ok(Some(Ksm { values }))
+    })
+    .await
+}
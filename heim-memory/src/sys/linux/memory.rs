@@ -1,46 +1,102 @@
-use std::io;
+use std::collections::HashMap;
 use std::str::FromStr;
 
 use heim_common::prelude::{Error, Result};
 use heim_common::units::{information, Information};
 use heim_runtime as rt;
 
-#[derive(Debug, Default, Eq, PartialEq, Copy, Clone)]
+/// Parsed `/proc/meminfo` contents.
+///
+/// Values expressed in kilobytes are stored as `Information`,
+/// unitless values (such as `HugePages_Total`) are stored as plain counters.
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
 pub struct Memory {
-    total: Information,     // MemTotal
-    free: Information,      // MemFree
-    available: Information, // MemAvailable
-    buffers: Information,   // Buffers
-    cached: Information,    // Cached
-    active: Information,    // Active
-    inactive: Information,  // Inactive
-    shared: Information,    // Shmem
+    sizes: HashMap<String, Information>,
+    counts: HashMap<String, u64>,
 }
 
 impl Memory {
     pub fn total(&self) -> Information {
-        self.total
+        self.size_or_zero("MemTotal")
     }
+
     pub fn free(&self) -> Information {
-        self.free
+        self.size_or_zero("MemFree")
     }
+
     pub fn available(&self) -> Information {
-        self.available
+        match self.size("MemAvailable") {
+            Some(available) => available,
+            // `MemAvailable` was added in Linux 3.14,
+            // falling back to the same estimation older `free(1)` versions used.
+            None => self.free() + self.buffers() + self.cached(),
+        }
     }
+
     pub fn buffers(&self) -> Information {
-        self.buffers
+        self.size_or_zero("Buffers")
     }
+
     pub fn cached(&self) -> Information {
-        self.cached
+        self.size_or_zero("Cached")
     }
+
     pub fn active(&self) -> Information {
-        self.active
+        self.size_or_zero("Active")
     }
+
     pub fn inactive(&self) -> Information {
-        self.inactive
+        self.size_or_zero("Inactive")
     }
+
     pub fn shared(&self) -> Information {
-        self.shared
+        self.size_or_zero("Shmem")
+    }
+
+    pub fn size(&self, key: &str) -> Option<Information> {
+        self.sizes.get(key).copied()
+    }
+
+    pub fn count(&self, key: &str) -> Option<u64> {
+        self.counts.get(key).copied()
+    }
+
+    fn size_or_zero(&self, key: &str) -> Information {
+        self.size(key)
+            .unwrap_or_else(|| Information::new::<information::byte>(0))
+    }
+}
+
+pub enum MemInfoValue {
+    Size(Information),
+    Count(u64),
+}
+
+// Parses one `/proc/meminfo` line into the key and value pair.
+//
+// Example:
+//
+// MemTotal:       16310304 kB
+// HugePages_Total:       0
+pub fn parse_meminfo_line(line: &str) -> Result<Option<(&str, MemInfoValue)>> {
+    let mut parts = line.splitn(2, ':');
+    let (key, value) = match (parts.next(), parts.next()) {
+        (Some(key), Some(value)) => (key, value),
+        _ => return Ok(None),
+    };
+
+    let mut value_parts = value.split_whitespace();
+    let value = match value_parts.next() {
+        Some(value) => value.parse::<u64>()?,
+        None => return Ok(None),
+    };
+
+    match value_parts.next() {
+        Some("kB") => Ok(Some((
+            key,
+            MemInfoValue::Size(Information::new::<information::kilobyte>(value)),
+        ))),
+        _ => Ok(Some((key, MemInfoValue::Count(value)))),
     }
 }
 
@@ -49,62 +105,66 @@ impl FromStr for Memory {
 
     fn from_str(meminfo: &str) -> Result<Self> {
         let mut memory = Memory::default();
-        let mut matched_lines = 0u8;
 
         for line in meminfo.lines() {
-            // If line does not starts with "Me", "Ac" or other options at all,
-            // we do not need that key at all
-            let first_bytes = &line.as_bytes()[..2];
-            match first_bytes {
-                b"Me" | b"Ac" | b"In" | b"Bu" | b"Ca" | b"Sh" => {}
-                _ => continue,
-            };
-
-            let mut parts = line.splitn(2, ':');
-            let field = match parts.next() {
-                Some("MemTotal") => &mut memory.total,
-                Some("MemFree") => &mut memory.free,
-                Some("MemAvailable") => &mut memory.available,
-                Some("Buffers") => &mut memory.buffers,
-                Some("Cached") => &mut memory.cached,
-                Some("Active") => &mut memory.active,
-                Some("Inactive") => &mut memory.inactive,
-                Some("Shmem") => &mut memory.shared,
-                _ => continue,
-            };
-
-            match parts.next() {
-                Some(value) => {
-                    *field = {
-                        let bytes = match value.trim_start().splitn(2, ' ').next() {
-                            Some(kbytes) => {
-                                let value = kbytes.parse::<u64>()?;
-                                Information::new::<information::kilobyte>(value)
-                            }
-                            None => continue,
-                        };
-
-                        matched_lines += 1;
-
-                        bytes
-                    }
+            match parse_meminfo_line(line)? {
+                Some((key, MemInfoValue::Size(value))) => {
+                    let _ = memory.sizes.insert(key.to_string(), value);
+                }
+                Some((key, MemInfoValue::Count(value))) => {
+                    let _ = memory.counts.insert(key.to_string(), value);
                 }
                 None => continue,
             }
+        }
 
-            if matched_lines == 8 {
-                return Ok(memory);
+        // All other keys are optional and might be missing in older kernels,
+        // but without these two there is nothing to report at all
+        for key in &["MemTotal", "MemFree"] {
+            if !memory.sizes.contains_key(*key) {
+                return Err(Error::missing_key(
+                    *key,
+                    format!("{}/meminfo", rt::linux::procfs_root().display()),
+                ));
             }
         }
 
-        // `FromStr` knows nothing about `/proc/meminfo`,
-        // but at this point we are not tracking which exact field are we missing.
-        // TODO: Rewrite parser and use `Error::missing_key` instead
-        let inner = io::Error::from(io::ErrorKind::InvalidData);
-        Err(Error::from(inner).with_file(rt::linux::procfs_root().join("meminfo")))
+        Ok(memory)
     }
 }
 
 pub async fn memory() -> Result<Memory> {
     rt::fs::read_into(rt::linux::procfs_root().join("meminfo")).await
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use heim_common::units::information;
+
+    use super::Memory;
+
+    #[test]
+    fn test_missing_keys() {
+        // Linux 2.6.32 has no `MemAvailable` key
+        const MEMINFO: &str = "MemTotal:        2048000 kB
+MemFree:          512000 kB
+Buffers:          100000 kB
+Cached:           400000 kB
+HugePages_Total:       4
+";
+
+        let memory = Memory::from_str(MEMINFO).unwrap();
+        assert_eq!(memory.total().get::<information::kilobyte>(), 2_048_000);
+        assert_eq!(memory.available().get::<information::kilobyte>(), 1_012_000);
+        assert_eq!(memory.shared().get::<information::byte>(), 0);
+        assert_eq!(memory.count("HugePages_Total"), Some(4));
+        assert_eq!(memory.size("HugePages_Total"), None);
+    }
+
+    #[test]
+    fn test_required_keys() {
+        assert!(Memory::from_str("MemFree: 512000 kB\n").is_err());
+    }
+}
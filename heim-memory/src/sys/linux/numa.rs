@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use heim_common::prelude::*;
+use heim_common::sys::linux::parse_cpu_list;
+use heim_runtime as rt;
+
+use super::{parse_meminfo_line, read_pools, MemInfoValue};
+use crate::os::linux::NumaNode;
+
+fn node_id(name: &str) -> Option<u32> {
+    name.strip_prefix("node")?.parse().ok()
+}
+
+fn node(id: u32, path: &Path) -> Result<NumaNode> {
+    let mut meminfo = HashMap::new();
+    // Example: `Node 0 MemTotal:        6147400 kB`
+    for line in fs::read_to_string(path.join("meminfo"))?.lines() {
+        let line = match line.splitn(3, ' ').nth(2) {
+            Some(line) => line,
+            None => continue,
+        };
+        if let Some((key, MemInfoValue::Size(value))) = parse_meminfo_line(line)? {
+            let _ = meminfo.insert(key.to_string(), value);
+        }
+    }
+
+    let mut numastat = HashMap::new();
+    for line in fs::read_to_string(path.join("numastat"))?.lines() {
+        let mut parts = line.split_whitespace();
+        if let (Some(key), Some(value)) = (parts.next(), parts.next()) {
+            let _ = numastat.insert(key.to_string(), value.parse::<u64>()?);
+        }
+    }
+
+    let distances = fs::read_to_string(path.join("distance"))?
+        .split_whitespace()
+        .map(|distance| distance.parse::<u32>().map_err(Error::from))
+        .collect::<Result<Vec<_>>>()?;
+
+    let cpus = parse_cpu_list(&fs::read_to_string(path.join("cpulist"))?)?;
+
+    let huge_pages = read_pools(&path.join("hugepages"))?
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(NumaNode {
+        id,
+        meminfo,
+        numastat,
+        distances,
+        cpus,
+        huge_pages,
+    })
+}
+
+pub async fn numa_nodes() -> Result<impl Stream<Item = Result<NumaNode>>> {
+    let nodes = rt::spawn_blocking(|| {
+        let root = rt::linux::sysfs_root().join("devices/system/node");
+        let entries = match fs::read_dir(&root) {
+            Ok(entries) => entries,
+            // Kernel was built without `CONFIG_NUMA`
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(Error::from(e).with_file(root)),
+        };
+
+        let mut ids = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            if let Some(id) = entry.file_name().to_str().and_then(node_id) {
+                ids.push(id);
+            }
+        }
+        ids.sort_unstable();
+
+        Ok(ids
+            .into_iter()
+            .map(|id| node(id, &root.join(format!("node{}", id))))
+            .collect::<Vec<_>>())
+    })
+    .await?;
+
+    Ok(stream::iter(nodes))
+}
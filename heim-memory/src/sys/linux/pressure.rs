@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use heim_common::prelude::*;
+use heim_common::sys::linux::cgroup::{self, Version};
+use heim_runtime as rt;
+
+use super::parse_vmstat;
+use crate::os::linux::PressureEvent;
+
+/// Polling interval will not go below this value while events keep coming.
+const MIN_INTERVAL: Duration = Duration::from_millis(100);
+
+// Returns `total` stall time in microseconds from the `some` line.
+//
+// Example:
+//
+// some avg10=0.00 avg60=0.00 avg300=0.00 total=12345
+// full avg10=0.00 avg60=0.00 avg300=0.00 total=6789
+fn parse_psi_total(contents: &str) -> Option<u64> {
+    contents
+        .lines()
+        .find(|line| line.starts_with("some "))?
+        .split_whitespace()
+        .find_map(|field| field.strip_prefix("total="))?
+        .parse()
+        .ok()
+}
+
+// Parses flat keyed files, ex. `memory.events`:
+//
+// low 0
+// high 12
+// max 3
+// oom 1
+// oom_kill 1
+fn parse_flat_keyed(contents: &str) -> HashMap<String, u64> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let key = parts.next()?;
+            let value = parts.next()?.parse().ok()?;
+
+            Some((key.to_string(), value))
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+enum Counters {
+    // cgroup v2 `memory.events` file, opened once since modification notifications
+    // are tracked per file description
+    Events(fs::File, PathBuf),
+    // Only the `oom_kill` counter is available system-wide
+    VmStat(PathBuf),
+}
+
+impl Counters {
+    fn read(&mut self) -> Result<HashMap<String, u64>> {
+        match self {
+            Counters::Events(file, path) => {
+                let mut contents = String::new();
+                let _ = file
+                    .seek(SeekFrom::Start(0))
+                    .and_then(|_| file.read_to_string(&mut contents))
+                    .map_err(|e| Error::from(e).with_file(path.as_path()))?;
+
+                Ok(parse_flat_keyed(&contents))
+            }
+            Counters::VmStat(path) => {
+                let contents = fs::read_to_string(&path)
+                    .map_err(|e| Error::from(e).with_file(path.as_path()))?;
+
+                parse_vmstat(&contents)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Watcher {
+    // PSI trigger, kernel notifies about the threshold crossing with `POLLPRI`
+    trigger: Option<fs::File>,
+    // Pressure file used for polling if trigger can't be created
+    psi: Option<PathBuf>,
+    last_stall: Option<u64>,
+    counters: Counters,
+    previous: HashMap<String, u64>,
+    stall: Duration,
+    window: Duration,
+    interval: Duration,
+    last_check: Instant,
+}
+
+// See `Documentation/accounting/psi.rst` in the kernel source.
+fn create_trigger(path: &Path, stall: Duration, window: Duration) -> io::Result<fs::File> {
+    let mut file = fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open(path)?;
+    let trigger = format!("some {} {}\0", stall.as_micros(), window.as_micros());
+    file.write_all(trigger.as_bytes())?;
+
+    Ok(file)
+}
+
+impl Watcher {
+    fn new(stall: Duration, window: Duration) -> Result<Watcher> {
+        let procfs_root = rt::linux::procfs_root();
+
+        let group = cgroup::cgroup(procfs_root, "memory")?
+            .filter(|group| group.version() == Version::V2)
+            // Root cgroup has no `memory.events` and `memory.pressure` files
+            .filter(|group| group.path().join("memory.events").exists());
+
+        let (psi, counters) = match group {
+            Some(group) => {
+                let path = group.path().join("memory.events");
+                let file = fs::File::open(&path).map_err(|e| Error::from(e).with_file(&path))?;
+
+                (
+                    group.path().join("memory.pressure"),
+                    Counters::Events(file, path),
+                )
+            }
+            None => (
+                procfs_root.join("pressure/memory"),
+                Counters::VmStat(procfs_root.join("vmstat")),
+            ),
+        };
+
+        // Kernel was built without `CONFIG_PSI` or it was disabled with `psi=0`
+        let psi = if psi.exists() { Some(psi) } else { None };
+        // Unprivileged processes are allowed to create triggers since Linux 6.5 only
+        // and only if window is a multiple of 2 seconds
+        let trigger = psi
+            .as_ref()
+            .and_then(|path| create_trigger(path, stall, window).ok());
+
+        let mut watcher = Watcher {
+            trigger,
+            psi,
+            last_stall: None,
+            counters,
+            previous: HashMap::new(),
+            stall,
+            window,
+            interval: window,
+            last_check: Instant::now(),
+        };
+        watcher.previous = watcher.counters.read()?;
+        watcher.last_stall = watcher.read_stall();
+
+        Ok(watcher)
+    }
+
+    fn read_stall(&self) -> Option<u64> {
+        let path = self.psi.as_ref()?;
+
+        parse_psi_total(&fs::read_to_string(path).ok()?)
+    }
+
+    fn is_notified(&self) -> bool {
+        self.trigger.is_some() && matches!(self.counters, Counters::Events(..))
+    }
+
+    // Blocks until kernel notifies about some changes or until polling interval passes.
+    // Timeout is always bounded, even if the kernel notifications are available,
+    // so the blocking thread does not outlive the dropped stream for too long.
+    // Returns `(trigger fired, counters file changed)` pair.
+    fn poll(&self) -> Result<(bool, bool)> {
+        let mut fds = Vec::with_capacity(2);
+        if let Some(trigger) = &self.trigger {
+            fds.push(libc::pollfd {
+                fd: trigger.as_raw_fd(),
+                events: libc::POLLPRI,
+                revents: 0,
+            });
+        }
+        if let Counters::Events(file, _) = &self.counters {
+            fds.push(libc::pollfd {
+                fd: file.as_raw_fd(),
+                events: libc::POLLPRI,
+                revents: 0,
+            });
+        }
+
+        let timeout = self.interval.as_millis().min(libc::c_int::MAX as u128) as libc::c_int;
+
+        loop {
+            // SAFETY: `fds` points to `fds.len()` initialized `pollfd` structs,
+            // which are referring to the file descriptors owned by `self`.
+            let result =
+                unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, timeout) };
+            if result >= 0 {
+                break;
+            }
+
+            let e = io::Error::last_os_error();
+            if e.kind() != io::ErrorKind::Interrupted {
+                return Err(Error::from(e).with_ffi("poll"));
+            }
+        }
+
+        let trigger_fired = match (&self.trigger, fds.first()) {
+            (Some(..), Some(fd)) => {
+                // Trigger is destroyed if the cgroup is removed
+                if fd.revents & libc::POLLERR != 0 {
+                    let inner = io::Error::from(io::ErrorKind::BrokenPipe);
+                    return Err(Error::from(inner).with_message("PSI trigger was destroyed"));
+                }
+                fd.revents & libc::POLLPRI != 0
+            }
+            _ => false,
+        };
+        let changed = match (&self.counters, fds.last()) {
+            (Counters::Events(..), Some(fd)) => fd.revents & libc::POLLPRI != 0,
+            _ => false,
+        };
+
+        Ok((trigger_fired, changed))
+    }
+
+    // Runs one polling round, returned events might be empty.
+    fn check(&mut self) -> Result<Vec<PressureEvent>> {
+        let (trigger_fired, changed) = self.poll()?;
+
+        let mut events = Vec::new();
+        if trigger_fired {
+            events.push(PressureEvent::ThresholdExceeded);
+        } else if self.trigger.is_none() {
+            let now = Instant::now();
+            let elapsed = now.duration_since(self.last_check);
+            let stall = self.read_stall();
+            if let (Some(previous), Some(current)) = (self.last_stall, stall) {
+                // Scaling the threshold to the actual elapsed time
+                let stalled = current.saturating_sub(previous) as f64;
+                let threshold = self.stall.as_micros() as f64 * elapsed.as_secs_f64()
+                    / self.window.as_secs_f64();
+                if stalled >= threshold {
+                    events.push(PressureEvent::ThresholdExceeded);
+                }
+            }
+            self.last_stall = stall;
+            self.last_check = now;
+        }
+
+        if changed || !self.is_notified() {
+            let current = self.counters.read()?;
+            let delta = |key: &str| {
+                let current = current.get(key).copied().unwrap_or(0);
+                let previous = self.previous.get(key).copied().unwrap_or(0);
+
+                current.saturating_sub(previous)
+            };
+            let (oom_kills, high, max) = (delta("oom_kill"), delta("high"), delta("max"));
+            if oom_kills > 0 {
+                events.push(PressureEvent::OomKill(oom_kills));
+            }
+            if high > 0 {
+                events.push(PressureEvent::HighLimit(high));
+            }
+            if max > 0 {
+                events.push(PressureEvent::MaxLimit(max));
+            }
+            self.previous = current;
+        }
+
+        // Polling more often while the memory pressure lasts
+        self.interval = if events.is_empty() {
+            (self.interval * 2).min(self.window)
+        } else {
+            (self.interval / 2).max(MIN_INTERVAL)
+        };
+
+        Ok(events)
+    }
+}
+
+pub async fn memory_pressure(
+    stall: Duration,
+    window: Duration,
+) -> Result<impl Stream<Item = Result<PressureEvent>>> {
+    if stall > window {
+        let inner = io::Error::from(io::ErrorKind::InvalidInput);
+        return Err(Error::from(inner).with_message("Stall time is longer than window"));
+    }
+
+    let watcher = rt::spawn_blocking(move || Watcher::new(stall, window)).await?;
+
+    let stream = stream::unfold(Some(watcher), |watcher| async move {
+        let mut watcher = watcher?;
+        loop {
+            // Polling rounds are short, so if the stream is dropped in between,
+            // watcher and its file descriptors are released after the current round
+            let (returned, events) = rt::spawn_blocking(move || {
+                let events = watcher.check();
+                (watcher, events)
+            })
+            .await;
+            watcher = returned;
+
+            match events {
+                Ok(events) if events.is_empty() => continue,
+                Ok(events) => {
+                    return Some((
+                        events.into_iter().map(Ok).collect::<Vec<_>>(),
+                        Some(watcher),
+                    ))
+                }
+                Err(e) => return Some((vec![Err(e)], None)),
+            }
+        }
+    });
+
+    Ok(stream.flat_map(stream::iter))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_flat_keyed, parse_psi_total};
+
+    #[test]
+    fn test_parse_psi_total() {
+        let psi = "some avg10=0.12 avg60=0.00 avg300=0.00 total=12345
+full avg10=0.00 avg60=0.00 avg300=0.00 total=6789
+";
+        assert_eq!(parse_psi_total(psi), Some(12345));
+        assert_eq!(parse_psi_total(""), None);
+    }
+
+    #[test]
+    fn test_parse_flat_keyed() {
+        let events = parse_flat_keyed("low 0\nhigh 12\nmax 3\noom 1\noom_kill 1\n");
+        assert_eq!(events["high"], 12);
+        assert_eq!(events["oom_kill"], 1);
+    }
+}
@@ -1,66 +1,23 @@
+use std::collections::HashMap;
 use std::fs;
 use std::io;
-use std::str::FromStr;
+use std::path::PathBuf;
 
 use heim_runtime as rt;
 
 use heim_common::prelude::*;
+use heim_common::sys::linux::unescape_octal;
 use heim_common::units::{information, Information};
 
-#[derive(Debug, Default, Clone)]
-pub struct VmStat {
-    swap_in: Option<Information>,  // pswpin
-    swap_out: Option<Information>, // pswpout
-}
-
-impl FromStr for VmStat {
-    type Err = Error;
-
-    fn from_str(vmstat: &str) -> Result<Self> {
-        let mut stat = VmStat::default();
-
-        for line in vmstat.lines() {
-            let first_bytes = &line.as_bytes()[..2];
-            if first_bytes != b"ps" {
-                continue;
-            }
-
-            let mut parts = line.splitn(2, ' ');
-            let field = match parts.next() {
-                Some("pswpin") => &mut stat.swap_in,
-                Some("pswpout") => &mut stat.swap_out,
-                _ => continue,
-            };
-
-            match parts.next() {
-                Some(value) => {
-                    *field = {
-                        let bytes = match value.trim_start().splitn(2, ' ').next() {
-                            Some(kbytes) => {
-                                // Values are expressed in 4 kilo bytes, we want bytes instead.
-                                // Source: psutil
-                                let value = kbytes.parse::<u64>()?;
-                                Information::new::<information::kilobyte>(4 * value)
-                            }
-                            None => continue,
-                        };
-
-                        Some(bytes)
-                    }
-                }
-                None => continue,
-            }
-        }
-
-        Ok(stat)
-    }
-}
+use super::{is_zram, parse_vmstat, zram_mm_stat};
+use crate::os::linux::{SwapDevice, SwapKind};
 
 #[derive(Debug, Clone)]
 pub struct Swap {
-    total: Information, // SwapTotal
-    free: Information,  // SwapFree
-    vm_stat: VmStat,
+    total: Information,            // SwapTotal
+    free: Information,             // SwapFree
+    swap_in: Option<Information>,  // pswpin
+    swap_out: Option<Information>, // pswpout
 }
 
 impl Swap {
@@ -77,18 +34,26 @@ impl Swap {
     }
 
     pub fn sin(&self) -> Option<Information> {
-        self.vm_stat.swap_in
+        self.swap_in
     }
 
     pub fn sout(&self) -> Option<Information> {
-        self.vm_stat.swap_out
+        self.swap_out
     }
 
-    pub fn parse_str(meminfo: &str, vm_stat: VmStat) -> Result<Self> {
+    pub fn parse_str(meminfo: &str, vmstat: &HashMap<String, u64>) -> Result<Self> {
+        // Values are expressed in 4 kilo bytes, we want bytes instead.
+        // Source: psutil
+        let pages = |key: &str| {
+            vmstat
+                .get(key)
+                .map(|value| Information::new::<information::kilobyte>(4 * value))
+        };
         let mut swap = Swap {
             total: Information::new::<information::byte>(0),
             free: Information::new::<information::byte>(0),
-            vm_stat,
+            swap_in: pages("pswpin"),
+            swap_out: pages("pswpout"),
         };
         let mut matched_lines = 0u8;
 
@@ -142,9 +107,112 @@ pub async fn swap() -> Result<Swap> {
     rt::spawn_blocking(|| {
         let meminfo = fs::read_to_string(rt::linux::procfs_root().join("meminfo"))?;
         let vmstat = fs::read_to_string(rt::linux::procfs_root().join("vmstat"))?;
-        let vmstat = VmStat::from_str(&vmstat)?;
+        let vmstat = parse_vmstat(&vmstat)?;
 
-        Swap::parse_str(&meminfo, vmstat)
+        Swap::parse_str(&meminfo, &vmstat)
     })
     .await
 }
+
+// Example:
+//
+// Filename                                Type            Size            Used            Priority
+// /dev/sda2                               partition       8388604         1024            -2
+// /swap\040file                          file            1048572         0               -3
+fn parse_swap_line(line: &str) -> Result<Option<SwapDevice>> {
+    let parts = line.split_whitespace().collect::<Vec<_>>();
+    if parts.len() < 5 {
+        return Ok(None);
+    }
+
+    // Whitespaces in the path are escaped, but kernel might append
+    // the unescaped ` (deleted)` suffix for the removed swap files
+    let (path, fields) = parts.split_at(parts.len() - 4);
+    let path = PathBuf::from(unescape_octal(&path.join(" ")).as_ref());
+    let kind = match fields[0] {
+        "partition" => match path.file_name().and_then(|name| name.to_str()) {
+            Some(name) if is_zram(name) => SwapKind::Zram,
+            _ => SwapKind::Partition,
+        },
+        "file" => SwapKind::File,
+        _ => return Ok(None),
+    };
+
+    // Sizes are expressed in kilobytes, same as in the `/proc/meminfo`
+    Ok(Some(SwapDevice {
+        path,
+        kind,
+        size: Information::new::<information::kilobyte>(fields[1].parse()?),
+        used: Information::new::<information::kilobyte>(fields[2].parse()?),
+        priority: fields[3].parse()?,
+        zram: None,
+    }))
+}
+
+pub async fn swap_devices() -> Result<impl Stream<Item = Result<SwapDevice>>> {
+    let devices = rt::spawn_blocking(|| {
+        let path = rt::linux::procfs_root().join("swaps");
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            // Kernel was built without `CONFIG_SWAP`
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(Error::from(e).with_file(path)),
+        };
+
+        let mut devices = Vec::new();
+        // First line is a header
+        for line in contents.lines().skip(1) {
+            match parse_swap_line(line) {
+                Ok(Some(mut device)) => {
+                    if device.kind == SwapKind::Zram {
+                        device.zram = device
+                            .path
+                            .file_name()
+                            .and_then(|name| name.to_str())
+                            .and_then(|name| zram_mm_stat(name).ok());
+                    }
+                    devices.push(Ok(device));
+                }
+                Ok(None) => continue,
+                Err(e) => devices.push(Err(e.with_file(&path))),
+            }
+        }
+
+        Ok(devices)
+    })
+    .await?;
+
+    Ok(stream::iter(devices))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use heim_common::units::information;
+
+    use super::parse_swap_line;
+    use crate::os::linux::SwapKind;
+
+    #[test]
+    fn test_parse_swap_line() {
+        let device = parse_swap_line(r"/swap\040file file 1048572 1024 -3")
+            .unwrap()
+            .unwrap();
+        assert_eq!(device.path(), Path::new("/swap file"));
+        assert_eq!(device.kind(), SwapKind::File);
+        assert_eq!(device.size().get::<information::kilobyte>(), 1_048_572);
+        assert_eq!(device.free().get::<information::kilobyte>(), 1_047_548);
+        assert_eq!(device.priority(), -3);
+
+        let device = parse_swap_line("/dev/zram0 partition 4194300 0 100")
+            .unwrap()
+            .unwrap();
+        assert_eq!(device.kind(), SwapKind::Zram);
+
+        let device = parse_swap_line(r"/old\040swap (deleted) file 1024 0 -2")
+            .unwrap()
+            .unwrap();
+        assert_eq!(device.path(), Path::new("/old swap (deleted)"));
+    }
+}
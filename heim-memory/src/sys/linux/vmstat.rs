@@ -0,0 +1,30 @@
+use std::collections::HashMap;
+
+use heim_common::prelude::*;
+use heim_runtime as rt;
+
+use crate::os::linux::VmStat;
+
+// Example:
+//
+// nr_free_pages 1543282
+// pgfault 1264883915
+pub fn parse_vmstat(vmstat: &str) -> Result<HashMap<String, u64>> {
+    let mut counters = HashMap::new();
+
+    for line in vmstat.lines() {
+        let mut parts = line.split_whitespace();
+        if let (Some(key), Some(value)) = (parts.next(), parts.next()) {
+            let _ = counters.insert(key.to_string(), value.parse::<u64>()?);
+        }
+    }
+
+    Ok(counters)
+}
+
+pub async fn vmstat() -> Result<VmStat> {
+    let contents = rt::fs::read_to_string(rt::linux::procfs_root().join("vmstat")).await?;
+    let counters = parse_vmstat(&contents)?;
+
+    Ok(VmStat { counters })
+}
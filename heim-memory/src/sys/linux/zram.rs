@@ -0,0 +1,194 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use heim_common::prelude::*;
+use heim_common::sys::linux::parse_selected;
+use heim_common::units::{information, Information};
+use heim_runtime as rt;
+
+use crate::os::linux::{ZramDevice, ZramIoStat, ZramMmStat};
+
+fn parse_values(contents: &str) -> Result<Vec<u64>> {
+    contents
+        .split_whitespace()
+        .map(|value| value.parse::<u64>().map_err(Error::from))
+        .collect()
+}
+
+// Example:
+//
+//  2154496   525423   917504        0   917504      120        0        0
+//
+// See `Documentation/admin-guide/blockdev/zram.rst` in the kernel source for fields description.
+pub fn parse_mm_stat(contents: &str) -> Result<ZramMmStat> {
+    let values = parse_values(contents)?;
+
+    // `mm_stat` was added in Linux 4.1 with seven fields,
+    // newer versions are only appending new ones
+    if values.len() < 7 {
+        let inner = io::Error::from(io::ErrorKind::InvalidData);
+        return Err(Error::from(inner).with_message(format!(
+            "Expected at least 7 fields in zram mm_stat, got {}",
+            values.len()
+        )));
+    }
+    let bytes = Information::new::<information::byte>;
+
+    Ok(ZramMmStat {
+        orig_data_size: bytes(values[0]),
+        compr_data_size: bytes(values[1]),
+        mem_used_total: bytes(values[2]),
+        mem_limit: match values[3] {
+            0 => None,
+            limit => Some(bytes(limit)),
+        },
+        mem_used_max: bytes(values[4]),
+        same_pages: values[5],
+        pages_compacted: values[6],
+        huge_pages: values.get(7).copied(),
+    })
+}
+
+// `name` is a block device name, ex. `zram0`
+pub fn zram_mm_stat(name: &str) -> Result<ZramMmStat> {
+    let path = rt::linux::sysfs_root()
+        .join("block")
+        .join(name)
+        .join("mm_stat");
+    let contents = fs::read_to_string(&path).map_err(|e| Error::from(e).with_file(&path))?;
+
+    parse_mm_stat(&contents)
+}
+
+pub fn is_zram(name: &str) -> bool {
+    match name.strip_prefix("zram") {
+        Some(id) => !id.is_empty() && id.bytes().all(|byte| byte.is_ascii_digit()),
+        None => false,
+    }
+}
+
+// Example:
+//
+//        0        0        0        0
+pub fn parse_io_stat(contents: &str) -> Result<ZramIoStat> {
+    match parse_values(contents)?.as_slice() {
+        [failed_reads, failed_writes, invalid_io, notify_free, ..] => Ok(ZramIoStat {
+            failed_reads: *failed_reads,
+            failed_writes: *failed_writes,
+            invalid_io: *invalid_io,
+            notify_free: *notify_free,
+        }),
+        values => {
+            let inner = io::Error::from(io::ErrorKind::InvalidData);
+            Err(Error::from(inner).with_message(format!(
+                "Expected at least 4 fields in zram io_stat, got {}",
+                values.len()
+            )))
+        }
+    }
+}
+
+fn device(name: String, path: &Path) -> Result<ZramDevice> {
+    let disk_size = path.join("disksize");
+    let disk_size = fs::read_to_string(&disk_size)
+        .map_err(Error::from)
+        .and_then(|size| size.trim().parse::<u64>().map_err(Error::from))
+        .map_err(|e| e.with_file(disk_size))?;
+
+    // Statistics files were added in Linux 4.1
+    let mm_stat = match fs::read_to_string(path.join("mm_stat")) {
+        Ok(contents) => Some(parse_mm_stat(&contents)?),
+        Err(..) => None,
+    };
+    let io_stat = match fs::read_to_string(path.join("io_stat")) {
+        Ok(contents) => Some(parse_io_stat(&contents)?),
+        Err(..) => None,
+    };
+    let algorithm = fs::read_to_string(path.join("comp_algorithm"))
+        .ok()
+        .as_deref()
+        .and_then(parse_selected)
+        .map(String::from);
+
+    Ok(ZramDevice {
+        name,
+        disk_size: Information::new::<information::byte>(disk_size),
+        algorithm,
+        mm_stat,
+        io_stat,
+    })
+}
+
+pub async fn zram_devices() -> Result<impl Stream<Item = Result<ZramDevice>>> {
+    let devices = rt::spawn_blocking(|| {
+        let root = rt::linux::sysfs_root().join("block");
+        let entries = match fs::read_dir(&root) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(Error::from(e).with_file(root)),
+        };
+
+        let mut names = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            if let Some(name) = entry.file_name().to_str() {
+                if is_zram(name) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        // Sorting by the device number, so `zram10` goes after the `zram9`
+        names.sort_unstable_by_key(|name| name[4..].parse::<u32>().unwrap_or(u32::MAX));
+
+        Ok(names
+            .into_iter()
+            .map(|name| {
+                let path = root.join(&name);
+                device(name, &path)
+            })
+            .collect::<Vec<_>>())
+    })
+    .await?;
+
+    Ok(stream::iter(devices))
+}
+
+#[cfg(test)]
+mod tests {
+    use heim_common::units::information;
+
+    use super::{is_zram, parse_io_stat, parse_mm_stat};
+
+    #[test]
+    fn test_parse_mm_stat() {
+        let stat = parse_mm_stat(
+            "2154496   525423   917504        0   917504      120        0        3\n",
+        )
+        .unwrap();
+
+        assert_eq!(stat.original_size().get::<information::byte>(), 2_154_496);
+        assert_eq!(stat.memory_limit(), None);
+        assert_eq!(stat.same_pages(), 120);
+        assert_eq!(stat.huge_pages(), Some(3));
+        assert!(stat.compression_ratio().is_some());
+
+        assert!(parse_mm_stat("0 0 0\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_io_stat() {
+        let stat = parse_io_stat("       1        2 3        4\n").unwrap();
+
+        assert_eq!(stat.failed_reads(), 1);
+        assert_eq!(stat.notify_free(), 4);
+        assert!(parse_io_stat("1 2\n").is_err());
+    }
+
+    #[test]
+    fn test_is_zram() {
+        assert!(is_zram("zram0"));
+        assert!(!is_zram("zram"));
+        assert!(!is_zram("sda1"));
+    }
+}
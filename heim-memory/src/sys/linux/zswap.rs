@@ -0,0 +1,45 @@
+use std::fs;
+use std::io;
+
+use heim_common::prelude::*;
+use heim_runtime as rt;
+
+use super::parse_vmstat;
+use crate::os::linux::Zswap;
+
+pub async fn zswap() -> Result<Option<Zswap>> {
+    let meminfo = super::memory().await?;
+
+    rt::spawn_blocking(move || {
+        let root = rt::linux::sysfs_root().join("module/zswap/parameters");
+        let enabled = match fs::read_to_string(root.join("enabled")) {
+            Ok(enabled) => enabled,
+            // Kernel was built without `CONFIG_ZSWAP`
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(Error::from(e).with_file(root.join("enabled"))),
+        };
+        let read = |name: &str| {
+            fs::read_to_string(root.join(name))
+                .ok()
+                .map(|value| value.trim().to_string())
+        };
+        let percent = |name: &str| read(name).and_then(|value| value.parse::<u64>().ok());
+
+        let path = rt::linux::procfs_root().join("vmstat");
+        let vmstat = fs::read_to_string(&path).map_err(|e| Error::from(e).with_file(&path))?;
+        let vmstat = parse_vmstat(&vmstat)?;
+
+        Ok(Some(Zswap {
+            enabled: enabled.trim() == "Y",
+            compressor: read("compressor"),
+            zpool: read("zpool"),
+            max_pool_percent: percent("max_pool_percent"),
+            accept_threshold_percent: percent("accept_threshold_percent"),
+            pool_size: meminfo.size("Zswap"),
+            stored: meminfo.size("Zswapped"),
+            stores: vmstat.get("zswpout").copied(),
+            loads: vmstat.get("zswpin").copied(),
+        }))
+    })
+    .await
+}
@@ -22,6 +22,11 @@ async fn smoke_memory() {
         let _ = mem.shared();
         let _ = mem.active();
         let _ = mem.inactive();
+        let _ = mem.dirty();
+        let _ = mem.slab_reclaimable();
+        let _ = mem.committed_as();
+        let _ = mem.huge_pages_total();
+        assert!(mem.get("MemTotal").is_some());
     }
 
     #[cfg(target_os = "macos")]
@@ -50,3 +55,191 @@ async fn smoke_swap() {
         let _ = swap.sout();
     }
 }
+
+#[heim_derive::test]
+#[cfg(target_os = "linux")]
+async fn smoke_vmstat() {
+    use heim_memory::os::linux::vmstat;
+
+    let before = vmstat().await.unwrap();
+    let after = vmstat().await.unwrap();
+
+    assert!(!after.counters().is_empty());
+    let _ = after.page_faults();
+    let _ = after.major_page_faults();
+    let _ = after.alloc_stalls();
+    let _ = after.oom_kills();
+    let _ = after.thp_fault_allocs();
+
+    let delta = after - before;
+    let _ = delta.get("pgfault");
+}
+
+#[heim_derive::test]
+#[cfg(target_os = "linux")]
+async fn smoke_numa_nodes() {
+    use heim_common::prelude::StreamExt;
+
+    let nodes = heim_memory::os::linux::numa_nodes().await.unwrap();
+    heim_runtime::pin!(nodes);
+    while let Some(node) = nodes.next().await {
+        let node = node.unwrap();
+
+        let _ = node.id();
+        let _ = node.total();
+        let _ = node.free();
+        let _ = node.file_pages();
+        let _ = node.anon_pages();
+        let _ = node.numa_hit();
+        let _ = node.huge_pages();
+        let _ = node.numa_miss();
+        let _ = node.numa_foreign();
+        let _ = node.distances();
+        let _ = node.cpus();
+    }
+}
+
+#[heim_derive::test]
+#[cfg(target_os = "linux")]
+async fn smoke_container_memory() {
+    let host = heim_memory::memory().await.unwrap();
+    let memory = heim_memory::os::linux::container_memory().await.unwrap();
+
+    assert!(memory.limit() <= host.total());
+    let _ = memory.limit_source().path();
+    let _ = memory.usage();
+    let _ = memory.usage_source();
+}
+
+#[heim_derive::test]
+#[cfg(target_os = "linux")]
+async fn smoke_swap_devices() {
+    use heim_common::prelude::StreamExt;
+
+    let devices = heim_memory::os::linux::swap_devices().await.unwrap();
+    heim_runtime::pin!(devices);
+    while let Some(device) = devices.next().await {
+        let device = device.unwrap();
+
+        let _ = device.path();
+        let _ = device.kind();
+        assert!(device.used() <= device.size());
+        let _ = device.priority();
+        if let Some(zram) = device.zram() {
+            let _ = zram.compression_ratio();
+        }
+    }
+}
+
+#[heim_derive::test]
+#[cfg(target_os = "linux")]
+async fn smoke_huge_pages() {
+    use heim_common::prelude::StreamExt;
+
+    let pools = heim_memory::os::linux::huge_pages().await.unwrap();
+    heim_runtime::pin!(pools);
+    while let Some(pool) = pools.next().await {
+        let pool = pool.unwrap();
+
+        let _ = pool.page_size();
+        assert!(pool.free() <= pool.total() + pool.surplus());
+        let _ = pool.used();
+        assert!(pool.reserved().is_some());
+    }
+
+    let thp = heim_memory::os::linux::transparent_huge_pages()
+        .await
+        .unwrap();
+    let _ = thp.enabled();
+    let _ = thp.defrag();
+    let _ = thp.page_size();
+    let _ = thp.anon();
+    let _ = thp.shmem();
+    let _ = thp.file();
+}
+
+#[heim_derive::test]
+#[cfg(target_os = "linux")]
+async fn smoke_buddyinfo() {
+    use heim_common::prelude::StreamExt;
+
+    let zones = heim_memory::os::linux::buddyinfo().await.unwrap();
+    heim_runtime::pin!(zones);
+    while let Some(zone) = zones.next().await {
+        let zone = zone.unwrap();
+
+        let _ = zone.node();
+        assert!(!zone.zone().is_empty());
+        assert!(!zone.free_blocks().is_empty());
+        assert!(zone.free_pages_at_order(3) <= zone.free_pages());
+        let _ = zone.fragmentation_index(3);
+        let _ = zone.free_blocks_by_type();
+        if let Some(watermarks) = zone.watermarks() {
+            assert!(watermarks.min() <= watermarks.high());
+        }
+    }
+}
+
+#[heim_derive::test]
+#[cfg(target_os = "linux")]
+async fn smoke_zram_devices() {
+    use heim_common::prelude::StreamExt;
+
+    let devices = heim_memory::os::linux::zram_devices().await.unwrap();
+    heim_runtime::pin!(devices);
+    while let Some(device) = devices.next().await {
+        let device = device.unwrap();
+
+        assert!(device.name().starts_with("zram"));
+        let _ = device.disk_size();
+        let _ = device.algorithm();
+        let _ = device.mm_stat();
+        if let Some(io_stat) = device.io_stat() {
+            let _ = io_stat.failed_reads();
+        }
+    }
+}
+
+#[heim_derive::test]
+#[cfg(target_os = "linux")]
+async fn smoke_zswap() {
+    if let Some(zswap) = heim_memory::os::linux::zswap().await.unwrap() {
+        let _ = zswap.is_enabled();
+        let _ = zswap.compressor();
+        let _ = zswap.max_pool_percent();
+        let _ = zswap.pool_size();
+        let _ = zswap.stores();
+    }
+}
+
+#[heim_derive::test]
+#[cfg(target_os = "linux")]
+async fn smoke_ksm() {
+    if let Some(ksm) = heim_memory::os::linux::ksm().await.unwrap() {
+        let _ = ksm.is_running();
+        let _ = ksm.pages_shared();
+        let _ = ksm.pages_sharing();
+        let _ = ksm.full_scans();
+        let _ = ksm.sharing_ratio();
+    }
+}
+
+#[heim_derive::test]
+#[cfg(target_os = "linux")]
+async fn smoke_memory_pressure() {
+    use std::time::Duration;
+
+    let stream =
+        heim_memory::os::linux::memory_pressure(Duration::from_millis(150), Duration::from_secs(1))
+            .await
+            .unwrap();
+    // Stream is infinite and might not yield anything at all on idle system
+    drop(stream);
+
+    assert!(heim_memory::os::linux::memory_pressure(
+        Duration::from_secs(2),
+        Duration::from_secs(1)
+    )
+    .await
+    .is_err());
+}
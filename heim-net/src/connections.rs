@@ -0,0 +1,266 @@
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+use heim_common::prelude::*;
+use heim_common::Pid;
+
+#[cfg(target_os = "linux")]
+use crate::sys;
+
+/// Kind of the sockets returned by [connections].
+///
+/// [connections]: ./fn.connections.html
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum ConnectionKind {
+    /// All sockets, including raw and Unix domain ones.
+    All,
+    /// TCP and UDP sockets over IPv4 and IPv6.
+    Inet,
+    /// TCP and UDP sockets over IPv4.
+    Inet4,
+    /// TCP and UDP sockets over IPv6.
+    Inet6,
+    /// TCP sockets over IPv4 and IPv6.
+    Tcp,
+    /// TCP sockets over IPv4.
+    Tcp4,
+    /// TCP sockets over IPv6.
+    Tcp6,
+    /// UDP sockets over IPv4 and IPv6.
+    Udp,
+    /// UDP sockets over IPv4.
+    Udp4,
+    /// UDP sockets over IPv6.
+    Udp6,
+    /// Raw IPv4 and IPv6 sockets.
+    Raw,
+    /// Unix domain sockets.
+    Unix,
+}
+
+impl ConnectionKind {
+    #[cfg(target_os = "linux")]
+    pub(crate) fn includes(self, family: AddressFamily, protocol: Protocol) -> bool {
+        let is_inet = matches!(protocol, Protocol::Tcp | Protocol::Udp);
+
+        match self {
+            ConnectionKind::All => true,
+            ConnectionKind::Inet => is_inet,
+            ConnectionKind::Inet4 => is_inet && family == AddressFamily::Inet,
+            ConnectionKind::Inet6 => is_inet && family == AddressFamily::Inet6,
+            ConnectionKind::Tcp => protocol == Protocol::Tcp,
+            ConnectionKind::Tcp4 => protocol == Protocol::Tcp && family == AddressFamily::Inet,
+            ConnectionKind::Tcp6 => protocol == Protocol::Tcp && family == AddressFamily::Inet6,
+            ConnectionKind::Udp => protocol == Protocol::Udp,
+            ConnectionKind::Udp4 => protocol == Protocol::Udp && family == AddressFamily::Inet,
+            ConnectionKind::Udp6 => protocol == Protocol::Udp && family == AddressFamily::Inet6,
+            ConnectionKind::Raw => protocol == Protocol::Raw,
+            ConnectionKind::Unix => family == AddressFamily::Unix,
+        }
+    }
+}
+
+/// Socket address family.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum AddressFamily {
+    /// IPv4 Internet protocols
+    Inet,
+    /// IPv6 Internet protocols
+    Inet6,
+    /// Unix domain sockets
+    Unix,
+}
+
+/// Socket protocol.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Protocol {
+    /// TCP
+    Tcp,
+    /// UDP
+    Udp,
+    /// Raw IP socket
+    Raw,
+    /// Unix domain stream socket (`SOCK_STREAM`)
+    UnixStream,
+    /// Unix domain datagram socket (`SOCK_DGRAM`)
+    UnixDatagram,
+    /// Unix domain sequenced packet socket (`SOCK_SEQPACKET`)
+    UnixSeqPacket,
+}
+
+/// Connection state.
+///
+/// TCP states are the same as in the `netstat` output.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum ConnectionState {
+    /// Connection is established.
+    Established,
+    /// Connection request was sent.
+    SynSent,
+    /// Connection request was received.
+    SynRecv,
+    /// Socket is closed and connection is shutting down.
+    FinWait1,
+    /// Connection is closed and socket waits for the remote end shutdown.
+    FinWait2,
+    /// Socket waits after close to handle packets still in the network.
+    TimeWait,
+    /// Socket is not used.
+    Close,
+    /// Remote end has shut down, waiting for the socket to close.
+    CloseWait,
+    /// Remote end has shut down and the socket is closed, waiting for the acknowledgement.
+    LastAck,
+    /// Socket is listening for incoming connections.
+    Listen,
+    /// Both sockets are shut down but not all data was sent.
+    Closing,
+    /// Connection request was received and is waiting in the listener queue.
+    NewSynRecv,
+}
+
+/// Network connection, or an open socket in general.
+#[derive(Debug, Clone)]
+pub struct Connection {
+    pub(crate) family: AddressFamily,
+    pub(crate) protocol: Protocol,
+    pub(crate) local_address: Option<SocketAddr>,
+    pub(crate) remote_address: Option<SocketAddr>,
+    pub(crate) path: Option<PathBuf>,
+    pub(crate) state: Option<ConnectionState>,
+    pub(crate) uid: Option<u32>,
+    pub(crate) pid: Option<Pid>,
+    // Exposed via the Linux-only `ConnectionExt` trait
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    pub(crate) inode: u64,
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    pub(crate) tx_queue: u64,
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    pub(crate) rx_queue: u64,
+}
+
+impl Connection {
+    /// Returns socket address family.
+    pub fn family(&self) -> AddressFamily {
+        self.family
+    }
+
+    /// Returns socket protocol.
+    pub fn protocol(&self) -> Protocol {
+        self.protocol
+    }
+
+    /// Returns local address.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` for Unix domain sockets, see [`path`] instead.
+    ///
+    /// [`path`]: #method.path
+    pub fn local_address(&self) -> Option<SocketAddr> {
+        self.local_address
+    }
+
+    /// Returns remote address.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` for Unix domain sockets and for not connected sockets,
+    /// ex. listening TCP or unconnected UDP ones.
+    pub fn remote_address(&self) -> Option<SocketAddr> {
+        self.remote_address
+    }
+
+    /// Returns Unix domain socket path.
+    ///
+    /// Abstract socket names are prefixed with the `@` symbol.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` for unnamed sockets and other address families.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Returns connection state.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` for UDP and raw sockets,
+    /// and for Unix domain sockets which are neither listening nor connected.
+    pub fn state(&self) -> Option<ConnectionState> {
+        self.state
+    }
+
+    /// Returns ID of the user owning the socket.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` for Unix domain sockets.
+    pub fn uid(&self) -> Option<u32> {
+        self.uid
+    }
+
+    /// Returns PID of the process owning the socket.
+    ///
+    /// ## Returns
+    ///
+    /// Returns `None` if PIDs resolution was skipped, see [`connections_without_pids`],
+    /// or if the owning process can't be inspected due to insufficient permissions.
+    /// If socket is shared by several processes, any of them is returned.
+    ///
+    /// [`connections_without_pids`]: ./fn.connections_without_pids.html
+    pub fn pid(&self) -> Option<Pid> {
+        self.pid
+    }
+}
+
+/// Returns a stream over the network [connections] of the given `kind`.
+///
+/// PIDs of the owning processes are resolved by scanning open file descriptors
+/// of all processes, which is quite expensive; use [`connections_without_pids`]
+/// if they are not needed.
+///
+/// ## Compatibility
+///
+/// Implemented only for Linux for now, where connections are read from the
+/// `/proc/net/{tcp,tcp6,udp,udp6,raw,raw6,unix}` files of the current network namespace.
+/// For other platforms will return an empty stream.
+///
+/// [connections]: ./struct.Connection.html
+/// [`connections_without_pids`]: ./fn.connections_without_pids.html
+pub async fn connections(kind: ConnectionKind) -> Result<impl Stream<Item = Result<Connection>>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::connections(kind, true).await
+        } else {
+            let _ = kind;
+
+            Ok(stream::iter(vec![]))
+        }
+    }
+}
+
+/// Returns a stream over the network [connections] of the given `kind`
+/// without resolving the owning processes PIDs.
+///
+/// See [connections](./fn.connections.html) for details.
+///
+/// [connections]: ./struct.Connection.html
+pub async fn connections_without_pids(
+    kind: ConnectionKind,
+) -> Result<impl Stream<Item = Result<Connection>>> {
+    cfg_if::cfg_if! {
+        if #[cfg(target_os = "linux")] {
+            sys::connections(kind, false).await
+        } else {
+            let _ = kind;
+
+            Ok(stream::iter(vec![]))
+        }
+    }
+}
@@ -27,9 +27,11 @@
 pub mod os;
 mod sys;
 
+mod connections;
 mod counters;
 mod nic;
 
+pub use self::connections::*;
 pub use self::counters::*;
 pub use self::nic::*;
 
@@ -0,0 +1,37 @@
+/// Linux-specific extension for [Connection].
+///
+/// [Connection]: ../../struct.Connection.html
+pub trait ConnectionExt {
+    /// Returns socket inode number, same as in the `socket:[inode]` links
+    /// of the `/proc/<pid>/fd/` directory.
+    ///
+    /// Sockets in the `TIME_WAIT` state are not bound to any inode and have `0` here.
+    fn inode(&self) -> u64;
+
+    /// Returns outgoing data queue size.
+    ///
+    /// For listening TCP sockets this is the backlog size,
+    /// always `0` for Unix domain sockets.
+    fn tx_queue(&self) -> u64;
+
+    /// Returns incoming data queue size.
+    ///
+    /// For listening TCP sockets this is the number of connections pending the `accept` call,
+    /// always `0` for Unix domain sockets.
+    fn rx_queue(&self) -> u64;
+}
+
+#[cfg(target_os = "linux")]
+impl ConnectionExt for crate::Connection {
+    fn inode(&self) -> u64 {
+        self.inode
+    }
+
+    fn tx_queue(&self) -> u64 {
+        self.tx_queue
+    }
+
+    fn rx_queue(&self) -> u64 {
+        self.rx_queue
+    }
+}
@@ -2,8 +2,10 @@
 //!
 //! Available only for `cfg(target_os = "linux")`
 
+mod connections;
 mod counters;
 mod nic;
 
+pub use self::connections::*;
 pub use self::counters::*;
 pub use self::nic::*;
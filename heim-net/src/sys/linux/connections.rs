@@ -0,0 +1,377 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+
+use heim_common::prelude::*;
+use heim_common::Pid;
+use heim_runtime as rt;
+
+use crate::{AddressFamily, Connection, ConnectionKind, ConnectionState, Protocol};
+
+const INET_TABLES: [(&str, AddressFamily, Protocol); 6] = [
+    ("tcp", AddressFamily::Inet, Protocol::Tcp),
+    ("tcp6", AddressFamily::Inet6, Protocol::Tcp),
+    ("udp", AddressFamily::Inet, Protocol::Udp),
+    ("udp6", AddressFamily::Inet6, Protocol::Udp),
+    ("raw", AddressFamily::Inet, Protocol::Raw),
+    ("raw6", AddressFamily::Inet6, Protocol::Raw),
+];
+
+// `__SO_ACCEPTCON` flag, set for the listening Unix sockets
+const UNIX_ACCEPTCON: u32 = 0x0001_0000;
+// `SS_CONNECTED` socket state
+const UNIX_CONNECTED: u32 = 0x03;
+
+fn invalid(line: &str) -> Error {
+    let inner = io::Error::from(io::ErrorKind::InvalidData);
+    Error::from(inner).with_message(format!("Invalid socket line: {}", line))
+}
+
+fn tcp_state(code: u32) -> Option<ConnectionState> {
+    let state = match code {
+        0x01 => ConnectionState::Established,
+        0x02 => ConnectionState::SynSent,
+        0x03 => ConnectionState::SynRecv,
+        0x04 => ConnectionState::FinWait1,
+        0x05 => ConnectionState::FinWait2,
+        0x06 => ConnectionState::TimeWait,
+        0x07 => ConnectionState::Close,
+        0x08 => ConnectionState::CloseWait,
+        0x09 => ConnectionState::LastAck,
+        0x0A => ConnectionState::Listen,
+        0x0B => ConnectionState::Closing,
+        0x0C => ConnectionState::NewSynRecv,
+        _ => return None,
+    };
+
+    Some(state)
+}
+
+// Addresses are printed as the hex dump of the `in_addr` / `in6_addr` structs,
+// each 32-bit word in the host byte order, while port is printed as a number.
+//
+// Example: `0100007F:0035` for `127.0.0.1:53` on little-endian hosts.
+fn parse_address(value: &str, family: AddressFamily) -> Option<SocketAddr> {
+    let mut parts = value.splitn(2, ':');
+    let (address, port) = (parts.next()?, parts.next()?);
+    let port = u16::from_str_radix(port, 16).ok()?;
+
+    let ip = match (family, address.len()) {
+        (AddressFamily::Inet, 8) => {
+            let word = u32::from_str_radix(address, 16).ok()?;
+            IpAddr::from(Ipv4Addr::from(word.to_ne_bytes()))
+        }
+        (AddressFamily::Inet6, 32) => {
+            let mut octets = [0u8; 16];
+            for (idx, chunk) in octets.chunks_mut(4).enumerate() {
+                let word = u32::from_str_radix(address.get(idx * 8..idx * 8 + 8)?, 16).ok()?;
+                chunk.copy_from_slice(&word.to_ne_bytes());
+            }
+            IpAddr::from(Ipv6Addr::from(octets))
+        }
+        _ => return None,
+    };
+
+    Some(SocketAddr::new(ip, port))
+}
+
+// Example:
+//
+//   sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+//    0: 0100007F:0035 00000000:0000 0A 00000000:00000000 00:00000000 00000000   101        0 20510 1 ...
+fn parse_inet_line(line: &str, family: AddressFamily, protocol: Protocol) -> Result<Connection> {
+    let parts = line.split_whitespace().collect::<Vec<_>>();
+    match parts.as_slice() {
+        [_sl, local, remote, state, queues, _timer, _retransmits, uid, _timeout, inode, ..] => {
+            let local_address = parse_address(local, family).ok_or_else(|| invalid(line))?;
+            let remote_address = parse_address(remote, family).ok_or_else(|| invalid(line))?;
+            let state = u32::from_str_radix(state, 16)?;
+
+            let mut queues = queues.splitn(2, ':');
+            let (tx_queue, rx_queue) = match (queues.next(), queues.next()) {
+                (Some(tx), Some(rx)) => {
+                    (u64::from_str_radix(tx, 16)?, u64::from_str_radix(rx, 16)?)
+                }
+                _ => return Err(invalid(line)),
+            };
+
+            Ok(Connection {
+                family,
+                protocol,
+                local_address: Some(local_address),
+                remote_address: if remote_address.ip().is_unspecified()
+                    && remote_address.port() == 0
+                {
+                    None
+                } else {
+                    Some(remote_address)
+                },
+                path: None,
+                state: match protocol {
+                    Protocol::Tcp => tcp_state(state),
+                    _ => None,
+                },
+                uid: Some(uid.parse()?),
+                pid: None,
+                inode: inode.parse()?,
+                tx_queue,
+                rx_queue,
+            })
+        }
+        _ => Err(invalid(line)),
+    }
+}
+
+// Example:
+//
+// Num       RefCount Protocol Flags    Type St Inode Path
+// 0000000000000000: 00000002 00000000 00010000 0001 01 20385 /run/systemd/private
+// 0000000000000000: 00000003 00000000 00000000 0001 03  1234
+//
+// Inode is padded with spaces and path is the rest of the line, which might contain spaces too.
+fn parse_unix_line(line: &str) -> Result<Connection> {
+    let mut fields = Vec::with_capacity(7);
+    let mut rest = line;
+    for _ in 0..7 {
+        rest = rest.trim_start();
+        let end = rest.find(' ').unwrap_or(rest.len());
+        fields.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+
+    match fields.as_slice() {
+        [_num, _ref_count, _protocol, flags, kind, state, inode] if !inode.is_empty() => {
+            let flags = u32::from_str_radix(flags, 16)?;
+            let state = u32::from_str_radix(state, 16)?;
+            let protocol = match u32::from_str_radix(kind, 16)? {
+                0x01 => Protocol::UnixStream,
+                0x02 => Protocol::UnixDatagram,
+                0x05 => Protocol::UnixSeqPacket,
+                _ => return Err(invalid(line)),
+            };
+            let path = match rest.get(1..) {
+                Some(path) if !path.is_empty() => Some(PathBuf::from(path)),
+                _ => None,
+            };
+
+            Ok(Connection {
+                family: AddressFamily::Unix,
+                protocol,
+                local_address: None,
+                remote_address: None,
+                path,
+                state: if flags & UNIX_ACCEPTCON != 0 {
+                    Some(ConnectionState::Listen)
+                } else if state == UNIX_CONNECTED {
+                    Some(ConnectionState::Established)
+                } else {
+                    None
+                },
+                uid: None,
+                pid: None,
+                inode: inode.parse()?,
+                tx_queue: 0,
+                rx_queue: 0,
+            })
+        }
+        _ => Err(invalid(line)),
+    }
+}
+
+fn read_table<F>(path: &Path, parse: F, connections: &mut Vec<Connection>) -> Result<()>
+where
+    F: Fn(&str) -> Result<Connection>,
+{
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        // Tables are missing if IPv6 is disabled or corresponding module is not loaded
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(Error::from(e).with_file(path)),
+    };
+
+    for line in contents
+        .lines()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+    {
+        connections.push(parse(line).map_err(|e| e.with_file(path))?);
+    }
+
+    Ok(())
+}
+
+// Maps socket inodes to the PIDs of processes having them open,
+// silently skipping processes which are gone or can't be inspected.
+fn socket_owners() -> HashMap<u64, Pid> {
+    let mut owners = HashMap::new();
+
+    let entries = match fs::read_dir(rt::linux::procfs_root()) {
+        Ok(entries) => entries,
+        Err(..) => return owners,
+    };
+    for entry in entries.flatten() {
+        let pid = match entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse().ok())
+        {
+            Some(pid) => pid,
+            None => continue,
+        };
+        let fds = match fs::read_dir(entry.path().join("fd")) {
+            Ok(fds) => fds,
+            Err(..) => continue,
+        };
+        for fd in fds.flatten() {
+            let inode = fs::read_link(fd.path()).ok().and_then(|link| {
+                link.to_str()?
+                    .strip_prefix("socket:[")?
+                    .strip_suffix(']')?
+                    .parse::<u64>()
+                    .ok()
+            });
+            if let Some(inode) = inode {
+                let _ = owners.entry(inode).or_insert(pid);
+            }
+        }
+    }
+
+    owners
+}
+
+fn read_connections(kind: ConnectionKind, resolve_pids: bool) -> Result<Vec<Connection>> {
+    let net = rt::linux::procfs_root().join("net");
+    let mut connections = Vec::new();
+
+    for &(name, family, protocol) in INET_TABLES.iter() {
+        if kind.includes(family, protocol) {
+            read_table(
+                &net.join(name),
+                |line| parse_inet_line(line, family, protocol),
+                &mut connections,
+            )?;
+        }
+    }
+    if kind.includes(AddressFamily::Unix, Protocol::UnixStream) {
+        read_table(&net.join("unix"), parse_unix_line, &mut connections)?;
+    }
+
+    if resolve_pids {
+        let owners = socket_owners();
+        for connection in connections.iter_mut() {
+            connection.pid = owners.get(&connection.inode).copied();
+        }
+    }
+
+    Ok(connections)
+}
+
+pub async fn connections(
+    kind: ConnectionKind,
+    resolve_pids: bool,
+) -> Result<impl Stream<Item = Result<Connection>>> {
+    let connections = rt::spawn_blocking(move || read_connections(kind, resolve_pids)).await?;
+
+    Ok(stream::iter(connections.into_iter().map(Ok)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::path::Path;
+
+    use super::{parse_inet_line, parse_unix_line};
+    use crate::{AddressFamily, ConnectionKind, ConnectionState, Protocol};
+
+    #[test]
+    #[cfg(target_endian = "little")]
+    fn test_parse_inet_line() {
+        let listen = parse_inet_line(
+            "   0: 0100007F:0035 00000000:0000 0A 00000000:00000002 00:00000000 00000000   101        0 20510 1 0000000000000000 100 0 0 10 0",
+            AddressFamily::Inet,
+            Protocol::Tcp,
+        )
+        .unwrap();
+        assert_eq!(
+            listen.local_address(),
+            Some("127.0.0.1:53".parse::<SocketAddr>().unwrap())
+        );
+        assert!(listen.remote_address().is_none());
+        assert_eq!(listen.state(), Some(ConnectionState::Listen));
+        assert_eq!(listen.uid(), Some(101));
+        assert_eq!(
+            (listen.inode, listen.tx_queue, listen.rx_queue),
+            (20510, 0, 2)
+        );
+
+        let established = parse_inet_line(
+            "   3: 0000000000000000FFFF00000F02000A:A4B2 0000000000000000FFFF00002202000A:01BB 01 00000000:00000000 02:00000A3E 00000000  1000        0 73021 2 0000000000000000 20 4 30 10 -1",
+            AddressFamily::Inet6,
+            Protocol::Tcp,
+        )
+        .unwrap();
+        assert_eq!(
+            established.local_address(),
+            Some("[::ffff:10.0.2.15]:42162".parse::<SocketAddr>().unwrap())
+        );
+        assert_eq!(
+            established.remote_address(),
+            Some("[::ffff:10.0.2.34]:443".parse::<SocketAddr>().unwrap())
+        );
+        assert_eq!(established.state(), Some(ConnectionState::Established));
+
+        let udp = parse_inet_line(
+            "  9: 00000000:0044 00000000:0000 07 00000000:00000000 00:00000000 00000000     0        0 18923 2 0000000000000000 0",
+            AddressFamily::Inet,
+            Protocol::Udp,
+        )
+        .unwrap();
+        assert!(udp.state().is_none());
+
+        assert!(
+            parse_inet_line("   0: 0100007F:0035", AddressFamily::Inet, Protocol::Tcp).is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_unix_line() {
+        let listen = parse_unix_line(
+            "0000000000000000: 00000002 00000000 00010000 0001 01 20385 /run/systemd/private",
+        )
+        .unwrap();
+        assert_eq!(listen.protocol(), Protocol::UnixStream);
+        assert_eq!(listen.state(), Some(ConnectionState::Listen));
+        assert_eq!(listen.path(), Some(Path::new("/run/systemd/private")));
+        assert_eq!(listen.inode, 20385);
+
+        let unnamed =
+            parse_unix_line("0000000000000000: 00000003 00000000 00000000 0002 03  1234").unwrap();
+        assert_eq!(unnamed.protocol(), Protocol::UnixDatagram);
+        assert_eq!(unnamed.state(), Some(ConnectionState::Established));
+        assert!(unnamed.path().is_none());
+        assert_eq!(unnamed.inode, 1234);
+
+        let spaced = parse_unix_line(
+            "0000000000000000: 00000002 00000000 00000000 0005 01 31337 @/tmp/my socket",
+        )
+        .unwrap();
+        assert_eq!(spaced.protocol(), Protocol::UnixSeqPacket);
+        assert!(spaced.state().is_none());
+        assert_eq!(spaced.path(), Some(Path::new("@/tmp/my socket")));
+
+        assert!(parse_unix_line("0000000000000000: 00000002 00000000").is_err());
+    }
+
+    #[test]
+    fn test_connection_kind() {
+        assert!(ConnectionKind::Inet.includes(AddressFamily::Inet6, Protocol::Udp));
+        assert!(!ConnectionKind::Inet.includes(AddressFamily::Inet, Protocol::Raw));
+        assert!(ConnectionKind::Tcp4.includes(AddressFamily::Inet, Protocol::Tcp));
+        assert!(!ConnectionKind::Tcp4.includes(AddressFamily::Inet6, Protocol::Tcp));
+        assert!(ConnectionKind::Unix.includes(AddressFamily::Unix, Protocol::UnixStream));
+        assert!(!ConnectionKind::Unix.includes(AddressFamily::Inet, Protocol::Tcp));
+        assert!(ConnectionKind::All.includes(AddressFamily::Inet6, Protocol::Raw));
+    }
+}
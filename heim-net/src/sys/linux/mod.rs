@@ -1,3 +1,5 @@
+mod connections;
 mod counters;
 
+pub use self::connections::*;
 pub use self::counters::*;
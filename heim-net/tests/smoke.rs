@@ -77,3 +77,42 @@ async fn smoke_nic() -> Result<()> {
 
     Ok(())
 }
+
+#[heim_derive::test]
+async fn smoke_connections() -> Result<()> {
+    let connections = net::connections(net::ConnectionKind::All).await?;
+    ::futures::pin_mut!(connections);
+    while let Some(connection) = connections.next().await {
+        let connection = connection?;
+
+        println!("Connection");
+        println!("  family: {:?}", connection.family());
+        println!("  protocol: {:?}", connection.protocol());
+        println!("  local_address: {:?}", connection.local_address());
+        println!("  remote_address: {:?}", connection.remote_address());
+        println!("  path: {:?}", connection.path());
+        println!("  state: {:?}", connection.state());
+        println!("  uid: {:?}", connection.uid());
+        println!("  pid: {:?}", connection.pid());
+
+        #[cfg(target_os = "linux")]
+        {
+            use heim_net::os::linux::ConnectionExt;
+
+            println!("  inode: {:?}", connection.inode());
+            println!("  tx_queue: {:?}", connection.tx_queue());
+            println!("  rx_queue: {:?}", connection.rx_queue());
+        }
+    }
+
+    let connections = net::connections_without_pids(net::ConnectionKind::Tcp).await?;
+    ::futures::pin_mut!(connections);
+    while let Some(connection) = connections.next().await {
+        let connection = connection?;
+
+        assert_eq!(connection.protocol(), net::Protocol::Tcp);
+        assert!(connection.pid().is_none());
+    }
+
+    Ok(())
+}
@@ -6,9 +6,11 @@ use crate::ProcessResult;
 
 mod io_counters;
 mod memory;
+mod mount_users;
 
 pub use self::io_counters::IoCountersExt;
 pub use self::memory::MemoryExt;
+pub use self::mount_users::{processes_using_mount, MountUse, MountUser};
 
 /// Linux-specific extension to [Process]
 ///
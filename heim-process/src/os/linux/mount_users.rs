@@ -0,0 +1,85 @@
+use std::path::{Path, PathBuf};
+
+use heim_common::prelude::*;
+
+use crate::{sys, Process, ProcessResult};
+
+/// The way process uses files on the mount.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum MountUse {
+    /// File is opened by the process.
+    OpenFile {
+        /// File descriptor number.
+        fd: u32,
+        /// Path to the opened file.
+        path: PathBuf,
+    },
+    /// Process current working directory is on the mount.
+    Cwd(PathBuf),
+    /// Process root directory, changed with `chroot`, is on the mount.
+    Root(PathBuf),
+    /// File is memory-mapped by the process, ex. executable or shared library.
+    Mmap(PathBuf),
+}
+
+/// Process using files on the mount.
+///
+/// See [processes_using_mount] for details.
+///
+/// [processes_using_mount]: ./fn.processes_using_mount.html
+#[derive(Debug)]
+pub struct MountUser {
+    pub(crate) process: Process,
+    pub(crate) uses: Vec<MountUse>,
+}
+
+impl MountUser {
+    /// Returns process which uses the mount.
+    pub fn process(&self) -> &Process {
+        &self.process
+    }
+
+    /// Returns all the ways this process uses the mount.
+    pub fn uses(&self) -> &[MountUse] {
+        &self.uses
+    }
+
+    /// Consumes `self` and returns the process.
+    pub fn into_process(self) -> Process {
+        self.process
+    }
+}
+
+/// Returns a stream over processes using files on the same filesystem as `path`,
+/// similar to the `fuser -m` command.
+///
+/// Usually `path` is a mount point, ex. one returned by the `heim_disk::Partition::mount_point`;
+/// `heim-process` does not depend on `heim-disk`, so partitions are not accepted directly.
+/// Files are matched by the device number, so bind mounts of the same filesystem are included,
+/// while other filesystems mounted below the `path` are not.
+///
+/// Open file descriptors, current and root directories and memory mappings
+/// are checked for each process via `/proc/<pid>/{fd,cwd,root,maps,map_files}` files.
+///
+/// ## Errors
+///
+/// Processes which can't be inspected due to insufficient permissions
+/// are yielded as the [`ProcessError::AccessDenied`] errors,
+/// so the caller knows that the result might be incomplete.
+/// Processes which exit during the scan are skipped.
+///
+/// [`ProcessError::AccessDenied`]: ../../enum.ProcessError.html#variant.AccessDenied
+pub async fn processes_using_mount<T>(
+    path: T,
+) -> Result<impl Stream<Item = ProcessResult<MountUser>>>
+where
+    T: AsRef<Path>,
+{
+    let stream = sys::processes_using_mount(path.as_ref().to_path_buf()).await?;
+
+    Ok(stream.map_ok(|(process, uses)| MountUser {
+        process: process.into(),
+        uses,
+    }))
+}
@@ -1,6 +1,8 @@
+mod mount_users;
 mod pids;
 mod process;
 
+pub use self::mount_users::*;
 pub use self::pids::*;
 pub use self::process::*;
 pub use crate::sys::unix::{Environment, EnvironmentIter, IntoEnvironmentIter};
@@ -0,0 +1,215 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::os::unix::fs::MetadataExt;
+use std::path::PathBuf;
+
+use heim_common::prelude::*;
+use heim_runtime as rt;
+
+use super::process::procfs::process_file_path;
+use super::{get, pids, Process};
+use crate::os::linux::MountUse;
+use crate::{Pid, ProcessError, ProcessResult};
+
+// Same encoding as glibc `gnu_dev_major` and `gnu_dev_minor` use
+fn dev_numbers(dev: u64) -> (u32, u32) {
+    let major = ((dev >> 8) & 0xfff) | ((dev >> 32) & !0xfff);
+    let minor = (dev & 0xff) | ((dev >> 12) & !0xff);
+
+    (major as u32, minor as u32)
+}
+
+#[derive(Debug, Eq, PartialEq)]
+struct Mapping<'a> {
+    range: &'a str,
+    major: u32,
+    minor: u32,
+    path: &'a str,
+}
+
+// Returns file-backed mappings only.
+//
+// Example:
+//
+// 7f2c4a1b1000-7f2c4a1b3000 r--p 00000000 08:01 1234                       /usr/lib/libc.so.6
+fn parse_maps_line(line: &str) -> Option<Mapping<'_>> {
+    let mut parts = line.splitn(6, ' ');
+    let range = parts.next()?;
+    let _perms = parts.next()?;
+    let _offset = parts.next()?;
+    let mut device = parts.next()?.splitn(2, ':');
+    let inode = parts.next()?;
+    let path = parts.next()?.trim_start();
+    if inode == "0" || path.is_empty() {
+        return None;
+    }
+
+    let major = u32::from_str_radix(device.next()?, 16).ok()?;
+    let minor = u32::from_str_radix(device.next()?, 16).ok()?;
+
+    Some(Mapping {
+        range,
+        major,
+        minor,
+        path,
+    })
+}
+
+// `major:minor` numbers in the `maps` file are the superblock ones, which are not matching
+// `st_dev` of the files on btrfs subvolumes and overlays, so mapped files are checked
+// the same way as the open ones: via the path as seen by the process
+// and via the `map_files` link, which requires `CAP_SYS_ADMIN`.
+// Device numbers are compared only if the mapped file can't be inspected, ex. it was deleted.
+fn is_mapped_from(pid: Pid, mapping: &Mapping<'_>, dev: u64) -> bool {
+    let mut candidates = Vec::with_capacity(2);
+    if !mapping.path.ends_with(" (deleted)") {
+        let relative = mapping.path.trim_start_matches('/');
+        candidates.push(process_file_path(pid, "root").join(relative));
+    }
+    candidates.push(process_file_path(pid, "map_files").join(mapping.range));
+
+    let devices = candidates
+        .iter()
+        .filter_map(|path| fs::metadata(path).ok())
+        .map(|metadata| metadata.dev())
+        .collect::<Vec<_>>();
+
+    if devices.is_empty() {
+        dev_numbers(dev) == (mapping.major, mapping.minor)
+    } else {
+        devices.contains(&dev)
+    }
+}
+
+type UseKind = fn(PathBuf) -> MountUse;
+
+fn process_error(pid: Pid, e: io::Error) -> ProcessError {
+    match e.kind() {
+        io::ErrorKind::NotFound => ProcessError::NoSuchProcess(pid),
+        io::ErrorKind::PermissionDenied => ProcessError::AccessDenied(pid),
+        _ => e.into(),
+    }
+}
+
+fn scan(pid: Pid, dev: u64) -> ProcessResult<Vec<MountUse>> {
+    let mut uses = Vec::new();
+
+    let links: [(&str, UseKind); 2] = [("cwd", MountUse::Cwd), ("root", MountUse::Root)];
+    for (name, kind) in links.iter() {
+        let path = process_file_path(pid, name);
+        let metadata = fs::metadata(&path).map_err(|e| process_error(pid, e))?;
+        if metadata.dev() == dev {
+            uses.push(kind(fs::read_link(&path).unwrap_or_default()));
+        }
+    }
+
+    let entries = fs::read_dir(process_file_path(pid, "fd")).map_err(|e| process_error(pid, e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| process_error(pid, e))?;
+        let fd = match entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse().ok())
+        {
+            Some(fd) => fd,
+            None => continue,
+        };
+        let metadata = match fs::metadata(entry.path()) {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+                return Err(ProcessError::AccessDenied(pid))
+            }
+            // File descriptor was closed in a meantime
+            Err(..) => continue,
+        };
+        if metadata.dev() == dev {
+            uses.push(MountUse::OpenFile {
+                fd,
+                path: fs::read_link(entry.path()).unwrap_or_default(),
+            });
+        }
+    }
+
+    let maps =
+        fs::read_to_string(process_file_path(pid, "maps")).map_err(|e| process_error(pid, e))?;
+    // Each library is usually mapped several times with different permissions
+    let mut checked = HashSet::new();
+    for mapping in maps.lines().filter_map(parse_maps_line) {
+        if checked.insert(mapping.path) && is_mapped_from(pid, &mapping, dev) {
+            uses.push(MountUse::Mmap(PathBuf::from(mapping.path)));
+        }
+    }
+
+    Ok(uses)
+}
+
+pub async fn processes_using_mount(
+    path: PathBuf,
+) -> Result<impl Stream<Item = ProcessResult<(Process, Vec<MountUse>)>>> {
+    let dev = rt::spawn_blocking(move || {
+        fs::metadata(&path)
+            .map(|metadata| metadata.dev())
+            .map_err(|e| Error::from(e).with_file(&path))
+    })
+    .await?;
+
+    let stream = pids()
+        .await?
+        .map_err(ProcessError::from)
+        .try_filter_map(move |pid| async move {
+            let uses = match rt::spawn_blocking(move || scan(pid, dev)).await {
+                Ok(uses) if uses.is_empty() => return Ok(None),
+                Ok(uses) => uses,
+                Err(ProcessError::NoSuchProcess(..)) => return Ok(None),
+                Err(e) => return Err(e),
+            };
+
+            match get(pid).await {
+                Ok(process) => Ok(Some((process, uses))),
+                Err(ProcessError::NoSuchProcess(..)) | Err(ProcessError::ZombieProcess(..)) => {
+                    Ok(None)
+                }
+                Err(e) => Err(e),
+            }
+        });
+
+    Ok(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dev_numbers, parse_maps_line, Mapping};
+
+    #[test]
+    fn test_parse_maps_line() {
+        assert_eq!(
+            parse_maps_line(
+                "7f2c4a1b1000-7f2c4a1b3000 r--p 00000000 fd:01 1234                       /usr/lib/libc.so.6"
+            ),
+            Some(Mapping {
+                range: "7f2c4a1b1000-7f2c4a1b3000",
+                major: 0xfd,
+                minor: 1,
+                path: "/usr/lib/libc.so.6",
+            })
+        );
+        assert_eq!(
+            parse_maps_line(
+                "7ffd1c5e6000-7ffd1c607000 rw-p 00000000 00:00 0                          [stack]"
+            ),
+            None
+        );
+        assert_eq!(
+            parse_maps_line("55d0c8a4e000-55d0c8a6f000 rw-p 00000000 00:00 0 "),
+            None
+        );
+    }
+
+    #[test]
+    fn test_dev_numbers() {
+        assert_eq!(dev_numbers(0x801), (8, 1));
+        assert_eq!(dev_numbers(0x10301), (259, 1));
+        assert_eq!(dev_numbers(0x10082c), (8, 300));
+    }
+}
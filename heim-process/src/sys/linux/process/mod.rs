@@ -15,7 +15,7 @@ use crate::sys::linux::process::procfs::process_file_path;
 use crate::sys::unix::{pid_kill, pid_priority, pid_setpriority, pid_wait};
 use crate::{Pid, ProcessError, ProcessResult, Status};
 
-mod procfs;
+pub mod procfs;
 
 pub use self::procfs::{Command, CommandIter, CpuTime, Environment, IoCounters, Memory};
 
@@ -92,3 +92,29 @@ async fn smoke_processes() -> Result<()> {
 
     Ok(())
 }
+
+#[heim_derive::test]
+#[cfg(target_os = "linux")]
+async fn smoke_processes_using_mount() -> Result<()> {
+    use heim_process::os::linux::{processes_using_mount, MountUse};
+
+    let current = process::current().await.unwrap();
+    let mut found = false;
+
+    let users = processes_using_mount(".").await?;
+    ::futures::pin_mut!(users);
+    while let Some(user) = users.next().await {
+        let user = match user {
+            Ok(user) => user,
+            Err(ProcessError::AccessDenied(..)) => continue,
+            Err(e) => panic!("{:#?}", e),
+        };
+
+        if *user.process() == current {
+            found = user.uses().iter().any(|u| matches!(u, MountUse::Cwd(..)));
+        }
+    }
+    assert!(found);
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
 #![cfg(target_os = "linux")]
 
-//! Linux-specific extension for configuring custom paths for Procfs
-/// and Sysfs.
+//! Linux-specific extension for configuring custom paths for Procfs,
+/// Sysfs and Devfs.
 use once_cell::sync::OnceCell;
 use std::borrow::Cow;
 use std::path::{Path, PathBuf};
@@ -12,6 +12,7 @@ type RootCell = OnceCell<PathBuf>;
 
 static PROCFS_ROOT: RootCell = OnceCell::new();
 static SYSFS_ROOT: RootCell = OnceCell::new();
+static DEVFS_ROOT: RootCell = OnceCell::new();
 
 /// Instantiates the OnceCell holding PROCFS_ROOT in the case that it hasn't
 /// already been instantiated.
@@ -44,3 +45,19 @@ pub fn set_sysfs_root<T: Into<Cow<'static, Path>>>(root: T) {
 pub fn sysfs_root() -> &'static Path {
     SYSFS_ROOT.get_or_init(|| PathBuf::from("/sys")).as_ref()
 }
+
+/// Instantiates the OnceCell holding DEVFS_ROOT in the case that it hasn't
+/// already been instantiated.
+///
+/// This can only be set ONCE per binary run.
+pub fn set_devfs_root<T: Into<Cow<'static, Path>>>(root: T) {
+    let root = root.into().into_owned();
+    let _ = DEVFS_ROOT.get_or_init(|| root);
+}
+
+/// Returns the static `Path` value of a configured DEVFS_ROOT.
+///
+/// If uninitialized, initializes the DEVFS_ROOT with the default path - `/dev`
+pub fn devfs_root() -> &'static Path {
+    DEVFS_ROOT.get_or_init(|| PathBuf::from("/dev")).as_ref()
+}
@@ -119,6 +119,8 @@ pub mod os {
         //! Available only for `cfg(target_os = "linux")`
         #[doc(inline)]
         #[cfg_attr(docsrs, doc(cfg(target_os = "linux")))]
-        pub use heim_runtime::linux::{procfs_root, set_procfs_root, set_sysfs_root, sysfs_root};
+        pub use heim_runtime::linux::{
+            devfs_root, procfs_root, set_devfs_root, set_procfs_root, set_sysfs_root, sysfs_root,
+        };
     }
 }